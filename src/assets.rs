@@ -4,6 +4,8 @@ use bevy_ecs_ldtk::prelude::LdtkProject;
 use bevy_kira_audio::AudioSource;
 use bevy_trickfilm::prelude::*;
 
+use crate::enemy::EnemyRoster;
+
 #[derive(AssetCollection, Resource)]
 pub struct GameAssets {
     // --- PLAYER ---
@@ -56,28 +58,8 @@ pub struct GameAssets {
     #[asset(paths("enemy/enemy_hit.trickfilm#main"), collection(typed))]
     pub enemy_hit_animations: Vec<Handle<AnimationClip2D>>,
 
-    #[asset(texture_atlas(tile_size_x = 96.0, tile_size_y = 80.0, columns = 8, rows = 2))]
-    #[asset(path = "enemy/bat/bat.png")]
-    pub enemy_bat: Handle<TextureAtlas>,
-    #[asset(paths("enemy/bat/bat.trickfilm#flying",), collection(typed))]
-    pub enemy_bat_animations: Vec<Handle<AnimationClip2D>>,
-
-    #[asset(path = "enemy/bat/bat_shadow.png")]
-    pub enemy_bat_shadow: Handle<Image>,
-
-    #[asset(texture_atlas(tile_size_x = 64.0, tile_size_y = 64.0, columns = 8, rows = 7))]
-    #[asset(path = "enemy/archer/archer.png")]
-    pub enemy_archer: Handle<TextureAtlas>,
-    #[asset(
-        paths(
-            "enemy/archer/archer.trickfilm#idling",
-            "enemy/archer/archer.trickfilm#walking",
-            "enemy/archer/archer.trickfilm#shooting",
-            "enemy/archer/archer.trickfilm#stunned",
-        ),
-        collection(typed)
-    )]
-    pub enemy_archer_animations: Vec<Handle<AnimationClip2D>>,
+    #[asset(path = "enemy/enemies.ron")]
+    pub enemy_roster: Handle<EnemyRoster>,
 
     #[asset(texture_atlas(tile_size_x = 80.0, tile_size_y = 16.0, columns = 1, rows = 3))]
     #[asset(path = "enemy/archer/projectile.png")]
@@ -85,9 +67,6 @@ pub struct GameAssets {
     #[asset(paths("enemy/archer/projectile.trickfilm#main",), collection(typed))]
     pub archer_projectile_animations: Vec<Handle<AnimationClip2D>>,
 
-    #[asset(path = "enemy/archer/archer_shadow.png")]
-    pub enemy_archer_shadow: Handle<Image>,
-
     // --- MAP ---
     #[asset(path = "map/level.ldtk")]
     pub level: Handle<LdtkProject>,
@@ -112,6 +91,9 @@ pub struct GameAssets {
     #[asset(path = "ui/score_icon.png")]
     pub score_icon: Handle<Image>,
 
+    #[asset(path = "ui/combo_bar_container.png")]
+    pub combo_bar_container: Handle<Image>,
+
     // --- MUSIC ---
     #[asset(path = "music/bgm.ogg")]
     pub bgm: Handle<AudioSource>,
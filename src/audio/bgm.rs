@@ -27,7 +27,7 @@ fn play_bgm(
     audio: Res<Audio>,
     game_audio: Res<GameAudio>,
 ) {
-    let volume = game_audio.main_volume * BGM_VOLUME;
+    let volume = game_audio.main_volume * game_audio.music_volume * BGM_VOLUME;
     let handle = audio
         .play(assets.bgm.clone())
         .with_volume(volume)
@@ -41,7 +41,7 @@ fn update_bgm_volumes(
     mut audio_instances: ResMut<Assets<AudioInstance>>,
     q_bgms: Query<&Bgm>,
 ) {
-    let volume = game_audio.main_volume * BGM_VOLUME;
+    let volume = game_audio.main_volume * game_audio.music_volume * BGM_VOLUME;
     for bgm in &q_bgms {
         if let Some(instance) = audio_instances.get_mut(bgm.handle.clone()) {
             instance.set_volume(volume, AudioTween::default());
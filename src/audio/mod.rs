@@ -6,7 +6,7 @@ pub use sound::PlaySound;
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::AudioPlugin;
 
-use crate::player::input::PlayerInput;
+use crate::{player::input::PlayerInput, settings::Settings};
 
 const MAIN_VOLUME_DELTA: f64 = 0.05;
 
@@ -19,27 +19,29 @@ impl Plugin for GameAudioPlugin {
     }
 }
 
+/// The volumes from the `Settings`.
 #[derive(Resource)]
 pub struct GameAudio {
     pub main_volume: f64,
+    pub music_volume: f64,
+    pub sound_volume: f64,
 }
 
 impl Default for GameAudio {
     fn default() -> Self {
-        Self { main_volume: 0.5 }
+        Self {
+            main_volume: 0.5,
+            music_volume: 1.0,
+            sound_volume: 1.0,
+        }
     }
 }
 
-impl GameAudio {
-    pub fn update(&mut self, x: f64) {
-        self.main_volume = (self.main_volume + x).clamp(0.0, 1.0);
-    }
-}
-
-fn update_main_volume(player_input: Res<PlayerInput>, mut game_audio: ResMut<GameAudio>) {
+fn update_main_volume(player_input: Res<PlayerInput>, mut settings: ResMut<Settings>) {
     if player_input.scroll == 0.0 {
         return;
     }
 
-    game_audio.update(-player_input.scroll as f64 * MAIN_VOLUME_DELTA);
+    settings.main_volume =
+        (settings.main_volume - player_input.scroll as f64 * MAIN_VOLUME_DELTA).clamp(0.0, 1.0);
 }
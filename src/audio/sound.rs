@@ -1,8 +1,10 @@
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 use bevy::{prelude::*, utils::HashSet};
 use bevy_kira_audio::prelude::{AudioSource, *};
 
+use crate::world::{determinism::AddTickEvent, seed::EffectRng};
+
 use super::GameAudio;
 
 #[derive(Event)]
@@ -34,9 +36,9 @@ fn play_sounds(
     mut commands: Commands,
     audio: Res<Audio>,
     game_audio: Res<GameAudio>,
+    mut effect_rng: ResMut<EffectRng>,
     mut ev_play_sound: EventReader<PlaySound>,
 ) {
-    let mut rng = thread_rng();
     let mut added_sounds: HashSet<Handle<AudioSource>> = HashSet::new();
 
     for ev in ev_play_sound.read() {
@@ -48,13 +50,15 @@ fn play_sounds(
         let speed_offset = if ev.rand_speed_intensity == 0.0 {
             0.0
         } else {
-            rng.gen_range(-1.0..1.0) * ev.rand_speed_intensity
+            effect_rng.gen_range(-1.0..1.0) * ev.rand_speed_intensity
         };
         let volume_offset = if ev.parent.is_some() { 0.0 } else { 1.0 };
 
         let mut audio_command = audio.play(ev.clip.clone());
         audio_command
-            .with_volume(ev.volume * volume_offset * game_audio.main_volume)
+            .with_volume(
+                ev.volume * volume_offset * game_audio.main_volume * game_audio.sound_volume,
+            )
             .with_playback_rate(ev.playback_rate + speed_offset);
 
         if ev.repeat {
@@ -92,7 +96,7 @@ pub struct GameSoundPlugin;
 
 impl Plugin for GameSoundPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<PlaySound>()
+        app.add_tick_event::<PlaySound>()
             .init_resource::<GameAudio>()
             .add_systems(Update, (play_sounds,));
     }
@@ -0,0 +1,145 @@
+//! Run the game headlessly with thousands of enemies and print how long the ticks take.
+//! Useful to check that the enemy systems scale, for instance after touching the `SpatialGrid`.
+//!
+//! Usage: `benchmark [--enemies <n>] [--ticks <n>] [--enemy <name>]`
+//!
+//! The enemies spawn in a ring around the player that is far enough away
+//! that none of them reaches the player before the benchmark ends.
+
+use std::f32::consts::TAU;
+use std::time::{Duration, Instant};
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+
+use insta_kill::enemy::{spawn_enemy, Enemy, EnemyRoster, EnemySpawning};
+use insta_kill::headless::{game_state, headless_plugins, prepare_app};
+use insta_kill::player::Player;
+use insta_kill::replay::DisableReplayRecording;
+use insta_kill::utils::storage::DisableStorage;
+use insta_kill::world::determinism::FrameStepping;
+use insta_kill::world::seed::SeedOverride;
+use insta_kill::{GameAssets, GamePlugin, GameState};
+
+const DEFAULT_ENEMIES: u32 = 2000;
+const DEFAULT_TICKS: u32 = 120;
+const DEFAULT_ENEMY: &str = "bat";
+const MIN_SPAWN_DISTANCE: f32 = 600.0;
+const MAX_SPAWN_DISTANCE: f32 = 1150.0;
+
+struct Args {
+    enemies: u32,
+    ticks: u32,
+    enemy: String,
+}
+
+impl Args {
+    fn parse() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |name: &str| {
+            args.iter()
+                .position(|arg| arg == name)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        Self {
+            enemies: value("--enemies")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_ENEMIES),
+            ticks: value("--ticks")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_TICKS),
+            enemy: value("--enemy")
+                .cloned()
+                .unwrap_or_else(|| DEFAULT_ENEMY.to_string()),
+        }
+    }
+}
+
+/// Spread the enemies evenly over the ring, the golden angle keeps them from lining up.
+fn spawn_enemies(app: &mut App, name: String, count: u32) -> Result<(), String> {
+    app.world.run_system_once(
+        move |mut commands: Commands,
+              assets: Res<GameAssets>,
+              rosters: Res<Assets<EnemyRoster>>,
+              q_player: Query<&Transform, With<Player>>| {
+            let player_pos = match q_player.get_single() {
+                Ok(r) => r.translation,
+                Err(_) => return Err("there is no player".to_string()),
+            };
+            let definition = match rosters
+                .get(&assets.enemy_roster)
+                .and_then(|roster| roster.get(&name))
+            {
+                Some(r) => r,
+                None => return Err(format!("there is no enemy called {}", name)),
+            };
+
+            let golden_angle = TAU * (1.0 - 1.0 / 1.618_034);
+            for i in 0..count {
+                let t = (i as f32 + 0.5) / count as f32;
+                let distance = MIN_SPAWN_DISTANCE + (MAX_SPAWN_DISTANCE - MIN_SPAWN_DISTANCE) * t;
+                let pos = player_pos
+                    + Quat::from_rotation_z(golden_angle * i as f32).mul_vec3(Vec3::X) * distance;
+                spawn_enemy(&mut commands, definition, pos);
+            }
+            Ok(())
+        },
+    )
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let mut app = App::new();
+    app.insert_resource(SeedOverride(Some(0)))
+        .insert_resource(FrameStepping { active: true })
+        .insert_resource(DisableReplayRecording)
+        .insert_resource(DisableStorage)
+        .insert_resource(EnemySpawning { active: false })
+        .add_plugins((headless_plugins(), GamePlugin));
+
+    if !prepare_app(&mut app) {
+        eprintln!("assets didn't finish loading");
+        return;
+    }
+    if let Err(err) = spawn_enemies(&mut app, args.enemy.clone(), args.enemies) {
+        eprintln!("failed to spawn enemies, {}", err);
+        return;
+    }
+    app.update();
+
+    let enemies = app.world.query::<&Enemy>().iter(&app.world).count();
+    let mut tick_times = Vec::new();
+    for tick in 0..args.ticks {
+        match game_state(&app) {
+            GameState::Gaming => {}
+            GameState::GameOver => {
+                eprintln!("the player died after {} ticks, the run ended early", tick);
+                break;
+            }
+            state => {
+                eprintln!("the run ended early after {} ticks, in {:?}", tick, state);
+                break;
+            }
+        }
+        let start = Instant::now();
+        app.update();
+        tick_times.push(start.elapsed());
+    }
+    if tick_times.is_empty() {
+        return;
+    }
+
+    tick_times.sort();
+    let total: Duration = tick_times.iter().sum();
+    let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    println!("{} enemies, {} ticks", enemies, tick_times.len());
+    println!(
+        "tick   mean {:>7.3}ms | median {:>7.3}ms | p99 {:>7.3}ms | max {:>7.3}ms",
+        ms(total) / tick_times.len() as f64,
+        ms(tick_times[tick_times.len() / 2]),
+        ms(tick_times[(tick_times.len() - 1) * 99 / 100]),
+        ms(tick_times[tick_times.len() - 1]),
+    );
+}
@@ -0,0 +1,427 @@
+//! A minimal stand-in for the leaderboard server, for testing score submissions locally.
+//!
+//! It speaks the JSON protocol of `insta_kill::protocol`, entries are only kept in memory.
+//! If a replay is attached to a submission, the run is re-simulated and the entry is
+//! rejected unless the outcome matches. Daily runs must use the seed of their date.
+//!
+//! Usage: `leaderboard_server [--port <port>] [--require-replay]`
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+
+use insta_kill::protocol::{LeaderboardResponse, RemoteEntry, ScoreSubmission, SubmissionResponse};
+use insta_kill::replay::{verify_replay, Replay};
+use insta_kill::world::daily::{daily_seed, DATE_FORMAT};
+
+const DEFAULT_PORT: u16 = 3434;
+/// Replays of very long runs are still far below this, anything bigger is rejected unread.
+const MAX_BODY_SIZE: usize = 4 * 1024 * 1024;
+/// The time is accumulated from frame deltas, so it's not exactly the same in every float format.
+const TIME_TOLERANCE: f32 = 0.1;
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 100;
+
+struct Entry {
+    id: u64,
+    name: String,
+    score: u32,
+    kills: u32,
+    time: f32,
+    submitted_at: DateTime<Utc>,
+}
+
+impl Entry {
+    fn to_remote(&self, rank: usize) -> RemoteEntry {
+        RemoteEntry {
+            id: self.id,
+            rank,
+            name: self.name.clone(),
+            score: self.score,
+            kills: self.kills,
+            time: self.time,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Leaderboard {
+    /// Sorted by score, older entries first on ties.
+    entries: Vec<Entry>,
+}
+
+impl Leaderboard {
+    fn insert(&mut self, id: u64, submission: ScoreSubmission) {
+        let index = self
+            .entries
+            .partition_point(|entry| entry.score >= submission.score);
+        self.entries.insert(
+            index,
+            Entry {
+                id,
+                name: submission.name,
+                score: submission.score,
+                kills: submission.kills,
+                time: submission.time,
+                submitted_at: Utc::now(),
+            },
+        );
+    }
+
+    fn response(&self, query: &Query) -> LeaderboardResponse {
+        let since = match query.period {
+            Period::AllTime => None,
+            Period::Week => Some(Utc::now() - Duration::days(7)),
+            Period::Today => Utc::now()
+                .date_naive()
+                .and_hms_opt(0, 0, 0)
+                .map(|midnight| midnight.and_utc()),
+        };
+        let entries: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| since.map_or(true, |since| entry.submitted_at >= since))
+            .collect();
+
+        let own = query.id.and_then(|id| {
+            let rank = entries.iter().position(|entry| entry.id == id)?;
+            Some(entries[rank].to_remote(rank))
+        });
+
+        LeaderboardResponse {
+            entries: entries
+                .iter()
+                .enumerate()
+                .skip(query.offset)
+                .take(query.limit)
+                .map(|(rank, entry)| entry.to_remote(rank))
+                .collect(),
+            total: entries.len(),
+            own,
+        }
+    }
+}
+
+/// The endless leaderboard and one leaderboard for every daily run.
+#[derive(Default)]
+struct Leaderboards {
+    endless: Leaderboard,
+    daily: HashMap<NaiveDate, Leaderboard>,
+    next_id: u64,
+}
+
+impl Leaderboards {
+    fn insert(&mut self, daily: Option<NaiveDate>, submission: ScoreSubmission) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let leaderboard = match daily {
+            Some(date) => self.daily.entry(date).or_default(),
+            None => &mut self.endless,
+        };
+        leaderboard.insert(id, submission);
+        id
+    }
+
+    fn response(&self, query: &Query) -> LeaderboardResponse {
+        let empty = Leaderboard::default();
+        let leaderboard = match query.daily {
+            Some(date) => self.daily.get(&date).unwrap_or(&empty),
+            None => &self.endless,
+        };
+        leaderboard.response(query)
+    }
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date, DATE_FORMAT)
+        .map_err(|err| format!("invalid date {}, {}", date, err))
+}
+
+enum Period {
+    AllTime,
+    Week,
+    Today,
+}
+
+/// The query parameters of `GET /leaderboard`.
+struct Query {
+    period: Period,
+    offset: usize,
+    limit: usize,
+    id: Option<u64>,
+    daily: Option<NaiveDate>,
+}
+
+impl Query {
+    fn parse(query: &str) -> Result<Self, String> {
+        let params: HashMap<String, String> = query
+            .split('&')
+            .filter(|param| !param.is_empty())
+            .map(|param| {
+                let (key, value) = param.split_once('=').unwrap_or((param, ""));
+                (percent_decode(key), percent_decode(value))
+            })
+            .collect();
+
+        let period = match params.get("period").map(String::as_str) {
+            None | Some("all") => Period::AllTime,
+            Some("week") => Period::Week,
+            Some("today") => Period::Today,
+            Some(period) => return Err(format!("unknown period {}", period)),
+        };
+        let number = |key: &str| -> Result<Option<u64>, String> {
+            params
+                .get(key)
+                .map(|value| value.parse().map_err(|_| format!("invalid {}", key)))
+                .transpose()
+        };
+
+        Ok(Self {
+            period,
+            offset: number("offset")?.unwrap_or_default() as usize,
+            limit: (number("limit")?.unwrap_or(DEFAULT_LIMIT as u64) as usize).min(MAX_LIMIT),
+            id: number("id")?,
+            daily: params
+                .get("daily")
+                .map(|date| parse_date(date))
+                .transpose()?,
+        })
+    }
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let hex = |i: usize| bytes.get(i).and_then(|b| (*b as char).to_digit(16));
+
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], hex(i + 1), hex(i + 2)) {
+            (b'%', Some(high), Some(low)) => {
+                decoded.push((high * 16 + low) as u8);
+                i += 3;
+            }
+            (b'+', _, _) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (byte, _, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+fn read_request(stream: &mut TcpStream) -> std::io::Result<Request> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            if key.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or_default();
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("body of {} bytes is too large", content_length),
+        ));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Request { method, path, body })
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nAccess-Control-Allow-Methods: GET, POST\r\nAccess-Control-Allow-Headers: Content-Type\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    if let Err(err) = stream.write_all(response.as_bytes()) {
+        eprintln!("failed to send response, {}", err);
+    }
+}
+
+fn respond_text(stream: &mut TcpStream, status: &str, body: &str) {
+    respond(stream, status, "text/plain", body);
+}
+
+fn respond_json(stream: &mut TcpStream, value: &impl Serialize) {
+    match serde_json::to_string(value) {
+        Ok(body) => respond(stream, "200 OK", "application/json", &body),
+        Err(err) => {
+            eprintln!("failed to serialize response, {}", err);
+            respond_text(stream, "500 Internal Server Error", "");
+        }
+    }
+}
+
+/// Re-simulate the attached replay and check that it matches the submission.
+fn verify_submission(submission: &ScoreSubmission, replay: &str) -> Result<(), String> {
+    let bytes = STANDARD
+        .decode(replay)
+        .map_err(|err| format!("invalid replay encoding, {}", err))?;
+    let replay = Replay::from_bytes(&bytes).map_err(|err| format!("invalid replay, {}", err))?;
+    if replay.seed != submission.seed {
+        return Err(format!(
+            "claimed seed {}, replay has seed {}",
+            submission.seed, replay.seed
+        ));
+    }
+    let summary = verify_replay(replay).map_err(|err| format!("failed to verify, {}", err))?;
+
+    if summary.score != submission.score
+        || summary.kills != submission.kills
+        || (summary.time - submission.time).abs() > TIME_TOLERANCE
+    {
+        return Err(format!(
+            "claimed {}/{}/{}, replay resulted in {}/{}/{}",
+            submission.score,
+            submission.kills,
+            submission.time,
+            summary.score,
+            summary.kills,
+            summary.time
+        ));
+    }
+    Ok(())
+}
+
+fn submit(
+    stream: &mut TcpStream,
+    leaderboards: &mut Leaderboards,
+    body: &[u8],
+    require_replay: bool,
+) {
+    let submission = match serde_json::from_slice::<ScoreSubmission>(body) {
+        Ok(r) => r,
+        Err(err) => {
+            respond_text(stream, "400 Bad Request", &err.to_string());
+            return;
+        }
+    };
+
+    let daily = match submission.daily.as_deref().map(parse_date).transpose() {
+        Ok(r) => r,
+        Err(err) => {
+            respond_text(stream, "400 Bad Request", &err);
+            return;
+        }
+    };
+    if let Some(date) = daily {
+        if submission.seed != daily_seed(date) {
+            respond_text(stream, "403 Forbidden", "not the seed of the daily run");
+            return;
+        }
+    }
+
+    match &submission.replay {
+        None if require_replay => {
+            respond_text(stream, "403 Forbidden", "a replay is required");
+            return;
+        }
+        None => println!("accepting unverified entry of {}", submission.name),
+        Some(replay) => {
+            if let Err(err) = verify_submission(&submission, replay) {
+                println!("rejecting entry of {}, {}", submission.name, err);
+                respond_text(stream, "403 Forbidden", &err);
+                return;
+            }
+            println!("verified entry of {}", submission.name);
+        }
+    }
+
+    let id = leaderboards.insert(daily, submission);
+    respond_json(stream, &SubmissionResponse { id });
+}
+
+fn handle(stream: &mut TcpStream, leaderboards: &mut Leaderboards, require_replay: bool) {
+    let request = match read_request(stream) {
+        Ok(r) => r,
+        Err(err) => {
+            eprintln!("failed to read request, {}", err);
+            if err.kind() == std::io::ErrorKind::InvalidData {
+                respond_text(stream, "413 Payload Too Large", &err.to_string());
+            }
+            return;
+        }
+    };
+
+    let (path, query) = request
+        .path
+        .split_once('?')
+        .unwrap_or((request.path.as_str(), ""));
+    if !path.trim_end_matches('/').ends_with("/leaderboard") {
+        respond_text(stream, "404 Not Found", "");
+        return;
+    }
+
+    match request.method.as_str() {
+        "GET" => match Query::parse(query) {
+            Ok(query) => respond_json(stream, &leaderboards.response(&query)),
+            Err(err) => respond_text(stream, "400 Bad Request", &err),
+        },
+        "POST" => submit(stream, leaderboards, &request.body, require_replay),
+        // The preflight of browsers, the CORS headers are part of every response.
+        "OPTIONS" => respond_text(stream, "204 No Content", ""),
+        _ => respond_text(stream, "405 Method Not Allowed", ""),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    let port = args
+        .iter()
+        .position(|arg| arg == "--port")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|port| port.parse().ok())
+        .unwrap_or(DEFAULT_PORT);
+    let require_replay = args.iter().any(|arg| arg == "--require-replay");
+
+    let listener = match TcpListener::bind(("127.0.0.1", port)) {
+        Ok(r) => r,
+        Err(err) => {
+            eprintln!("failed to bind to port {}, {}", port, err);
+            return;
+        }
+    };
+    println!("leaderboard server listening on 127.0.0.1:{}", port);
+
+    let mut leaderboards = Leaderboards::default();
+    for stream in listener.incoming() {
+        match stream {
+            Ok(mut stream) => handle(&mut stream, &mut leaderboards, require_replay),
+            Err(err) => eprintln!("failed to accept connection, {}", err),
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Run the game headlessly with a simple AI player and print statistics over many runs.
+//! Useful for balancing things like enemy spawn rates without playing hundreds of rounds.
+//!
+//! Usage: `simulate [--runs <n>] [--seed <first seed>] [--max-time <seconds>] [--verbose]`
+//!
+//! Run `i` uses the seed `first seed + i`, so the results are reproducible.
+
+use bevy::prelude::*;
+
+use insta_kill::enemy::{Enemy, EnemyProjectile};
+use insta_kill::headless::{game_state, headless_plugins, prepare_app, RunSummary};
+use insta_kill::player::input::{PlayerInput, PlayerInputSet};
+use insta_kill::player::Player;
+use insta_kill::replay::DisableReplayRecording;
+use insta_kill::utils::storage::DisableStorage;
+use insta_kill::world::determinism::{FrameStepping, TICK_RATE};
+use insta_kill::world::seed::SeedOverride;
+use insta_kill::{GamePlugin, GameState};
+
+const DEFAULT_RUNS: u32 = 100;
+const DEFAULT_MAX_TIME: f32 = 600.0;
+
+/// Roughly how far the strike reaches.
+const STRIKE_RANGE: f32 = 70.0;
+/// Projectiles closer than this get struck to reflect them.
+const PROJECTILE_DANGER_RANGE: f32 = 120.0;
+const DASH_MIN_DISTANCE: f32 = 150.0;
+const DASH_MAX_DISTANCE: f32 = 300.0;
+
+struct Args {
+    runs: u32,
+    first_seed: u32,
+    max_time: f32,
+    verbose: bool,
+}
+
+impl Args {
+    fn parse() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let value = |name: &str| {
+            args.iter()
+                .position(|arg| arg == name)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        Self {
+            runs: value("--runs")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_RUNS),
+            first_seed: value("--seed").and_then(|v| v.parse().ok()).unwrap_or(0),
+            max_time: value("--max-time")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MAX_TIME),
+            verbose: args.iter().any(|arg| arg == "--verbose"),
+        }
+    }
+}
+
+fn nearest<'a>(origin: Vec2, positions: impl Iterator<Item = &'a Transform>) -> Option<Vec2> {
+    positions
+        .map(|transform| transform.translation.truncate())
+        .min_by(|a, b| {
+            a.distance_squared(origin)
+                .total_cmp(&b.distance_squared(origin))
+        })
+}
+
+/// Go for the closest enemy, strike it when it's in range and dash towards it
+/// if it's a bit further away. Projectiles that come close get reflected.
+fn drive_player(
+    mut player_input: ResMut<PlayerInput>,
+    q_player: Query<&Transform, With<Player>>,
+    q_enemies: Query<(&Transform, &Enemy), Without<Player>>,
+    q_projectiles: Query<(&Transform, &EnemyProjectile), Without<Player>>,
+) {
+    let player_pos = match q_player.get_single() {
+        Ok(r) => r.translation.truncate(),
+        Err(_) => return,
+    };
+
+    *player_input = PlayerInput::default();
+
+    let projectile = nearest(
+        player_pos,
+        q_projectiles
+            .iter()
+            .filter(|(_, projectile)| !projectile.disabled)
+            .map(|(transform, _)| transform),
+    )
+    .filter(|pos| pos.distance(player_pos) < PROJECTILE_DANGER_RANGE);
+    if let Some(pos) = projectile {
+        player_input.aim_stick = Some((pos - player_pos).normalize_or_zero());
+        player_input.attack = true;
+        return;
+    }
+
+    let enemy = nearest(
+        player_pos,
+        q_enemies
+            .iter()
+            .filter(|(_, enemy)| !enemy.disabled)
+            .map(|(transform, _)| transform),
+    );
+    let target = match enemy {
+        Some(r) => r,
+        None => return,
+    };
+
+    let dir = (target - player_pos).normalize_or_zero();
+    let distance = target.distance(player_pos);
+    player_input.aim_stick = Some(dir);
+    player_input.move_direction = dir;
+    player_input.attack = distance < STRIKE_RANGE;
+    player_input.dash = (DASH_MIN_DISTANCE..DASH_MAX_DISTANCE).contains(&distance);
+}
+
+fn run_until_game_over(app: &mut App, max_ticks: usize) {
+    for _ in 0..max_ticks {
+        if game_state(app) != GameState::Gaming {
+            return;
+        }
+        app.update();
+    }
+}
+
+fn restart(app: &mut App, seed: u32) {
+    // Runs that hit the time limit are still going, end them properly first.
+    if game_state(app) == GameState::Gaming {
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::GameOver);
+        app.update();
+    }
+
+    app.world.resource_mut::<SeedOverride>().0 = Some(seed);
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Restart);
+    while game_state(app) != GameState::Gaming {
+        app.update();
+    }
+}
+
+fn percentile(sorted: &[f32], p: f32) -> f32 {
+    let index = ((sorted.len() - 1) as f32 * p).round() as usize;
+    sorted[index]
+}
+
+fn print_distribution(name: &str, mut values: Vec<f32>) {
+    values.sort_by(f32::total_cmp);
+    let mean = values.iter().sum::<f32>() / values.len() as f32;
+    println!(
+        "{:<6} mean {:>9.1} | min {:>9.1} | p25 {:>9.1} | median {:>9.1} | p75 {:>9.1} | max {:>9.1}",
+        name,
+        mean,
+        values[0],
+        percentile(&values, 0.25),
+        percentile(&values, 0.5),
+        percentile(&values, 0.75),
+        values[values.len() - 1],
+    );
+}
+
+fn main() {
+    let args = Args::parse();
+    if args.runs == 0 {
+        return;
+    }
+
+    let mut app = App::new();
+    app.insert_resource(SeedOverride(Some(args.first_seed)))
+        .insert_resource(FrameStepping { active: true })
+        .insert_resource(DisableReplayRecording)
+        .insert_resource(DisableStorage)
+        .add_plugins((headless_plugins(), GamePlugin))
+        .add_systems(PreUpdate, drive_player.after(PlayerInputSet));
+
+    if !prepare_app(&mut app) {
+        eprintln!("assets didn't finish loading");
+        return;
+    }
+
+    let max_ticks = (args.max_time as f64 * TICK_RATE) as usize;
+    let mut summaries = Vec::new();
+    let mut timed_out = 0;
+    for i in 0..args.runs {
+        let seed = args.first_seed.wrapping_add(i);
+        // The first run already started with the first seed.
+        if i > 0 {
+            restart(&mut app, seed);
+        }
+
+        run_until_game_over(&mut app, max_ticks);
+        if game_state(&app) == GameState::Gaming {
+            timed_out += 1;
+        }
+
+        let summary = RunSummary::from_world(&app.world);
+        if args.verbose {
+            println!(
+                "seed {:>10} | score {:>7} | kills {:>5} | time {:>7.1}s",
+                seed, summary.score, summary.kills, summary.time
+            );
+        }
+        summaries.push(summary);
+    }
+
+    println!(
+        "{} runs, {} hit the time limit of {}s",
+        args.runs, timed_out, args.max_time
+    );
+    print_distribution("score", summaries.iter().map(|s| s.score as f32).collect());
+    print_distribution("kills", summaries.iter().map(|s| s.kills as f32).collect());
+    print_distribution("time", summaries.iter().map(|s| s.time).collect());
+}
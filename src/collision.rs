@@ -0,0 +1,166 @@
+//! Turns the raw rapier `CollisionEvent`s between colliders into typed events between
+//! the entities that own the colliders.
+//!
+//! Colliders are always children of the entity they belong to (the player, an enemy,
+//! a strike...), the router resolves them once per tick so that the gameplay systems
+//! only have to read the events they care about.
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    enemy::{Enemy, EnemyProjectile, HitSource},
+    player::{
+        dash::DashLanding, hook::PlayerHook, reflection_projectile::ReflectionProjectile,
+        spawn::PlayerDashColliderContainer, strike::Strike, Player,
+    },
+    world::determinism::AddTickEvent,
+    GameplaySet,
+};
+
+/// Read the routed events after this set.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CollisionRouterSet;
+
+/// An attack of the player touched an enemy.
+/// The hit didn't go through `EnemyVulnerability` yet, so the enemy may still block it.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct HitEnemy {
+    pub enemy: Entity,
+    pub source: HitSource,
+    /// The strike, reflection projectile, dash or landing that hit the enemy.
+    pub by: Entity,
+    /// Where the attack was when it hit.
+    pub pos: Vec2,
+}
+
+/// A strike touched an enemy projectile.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct ProjectileStruck {
+    pub projectile: Entity,
+    pub strike: Entity,
+}
+
+/// An enemy or an enemy projectile touched the player.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PlayerHit {
+    pub by: Entity,
+}
+
+/// The hook touched an enemy.
+#[derive(Event, Clone, Copy, Debug)]
+pub struct HookAttached {
+    pub enemy: Entity,
+    pub hook: Entity,
+    pub pos: Vec2,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Owner {
+    Player,
+    Enemy,
+    EnemyProjectile,
+    Attack(HitSource),
+    Hook,
+}
+
+fn route_collisions(
+    q_parents: Query<&Parent, With<Collider>>,
+    q_owners: Query<(
+        &GlobalTransform,
+        Has<Player>,
+        Has<Enemy>,
+        Has<EnemyProjectile>,
+        Has<Strike>,
+        Has<ReflectionProjectile>,
+        Has<PlayerDashColliderContainer>,
+        Has<DashLanding>,
+        Has<PlayerHook>,
+    )>,
+    mut ev_collision_events: EventReader<CollisionEvent>,
+    mut ev_hit_enemy: EventWriter<HitEnemy>,
+    mut ev_projectile_struck: EventWriter<ProjectileStruck>,
+    mut ev_player_hit: EventWriter<PlayerHit>,
+    mut ev_hook_attached: EventWriter<HookAttached>,
+) {
+    let owner = |collider: Entity| -> Option<(Entity, Owner, Vec2)> {
+        let entity = q_parents.get(collider).ok()?.get();
+        let (transform, player, enemy, projectile, strike, reflection, dash, landing, hook) =
+            q_owners.get(entity).ok()?;
+
+        let owner = if player {
+            Owner::Player
+        } else if enemy {
+            Owner::Enemy
+        } else if projectile {
+            Owner::EnemyProjectile
+        } else if strike {
+            Owner::Attack(HitSource::Strike)
+        } else if reflection {
+            Owner::Attack(HitSource::Reflection)
+        } else if dash {
+            Owner::Attack(HitSource::Dash)
+        } else if landing {
+            Owner::Attack(HitSource::DashLanding)
+        } else if hook {
+            Owner::Hook
+        } else {
+            return None;
+        };
+        Some((entity, owner, transform.translation().truncate()))
+    };
+
+    for ev in ev_collision_events.read() {
+        let (source, target) = match ev {
+            CollisionEvent::Started(source, target, _) => (*source, *target),
+            CollisionEvent::Stopped(_, _, _) => continue,
+        };
+
+        let (a, b) = match (owner(source), owner(target)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => continue,
+        };
+
+        for ((entity, owner, pos), (other, other_owner, _)) in [(a, b), (b, a)] {
+            match (owner, other_owner) {
+                (Owner::Attack(source), Owner::Enemy) => ev_hit_enemy.send(HitEnemy {
+                    enemy: other,
+                    source,
+                    by: entity,
+                    pos,
+                }),
+                (Owner::Attack(HitSource::Strike), Owner::EnemyProjectile) => ev_projectile_struck
+                    .send(ProjectileStruck {
+                        projectile: other,
+                        strike: entity,
+                    }),
+                (Owner::Enemy | Owner::EnemyProjectile, Owner::Player) => {
+                    ev_player_hit.send(PlayerHit { by: entity })
+                }
+                (Owner::Hook, Owner::Enemy) => ev_hook_attached.send(HookAttached {
+                    enemy: other,
+                    hook: entity,
+                    pos,
+                }),
+                _ => {}
+            }
+        }
+    }
+}
+
+pub struct CollisionRouterPlugin;
+
+impl Plugin for CollisionRouterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_tick_event::<HitEnemy>()
+            .add_tick_event::<ProjectileStruck>()
+            .add_tick_event::<PlayerHit>()
+            .add_tick_event::<HookAttached>()
+            .add_systems(
+                FixedUpdate,
+                (route_collisions,)
+                    .in_set(CollisionRouterSet)
+                    .in_set(GameplaySet),
+            );
+    }
+}
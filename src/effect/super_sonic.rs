@@ -1,7 +1,11 @@
 use bevy::prelude::*;
 use bevy_trickfilm::prelude::*;
 
-use crate::{utils::quat_from_vec2, world::camera::YSort, GameAssets, GameState};
+use crate::{
+    utils::quat_from_vec2,
+    world::{camera::YSort, determinism::AddTickEvent},
+    GameAssets, GameplaySet,
+};
 
 #[derive(Component)]
 struct SuperSonic;
@@ -55,8 +59,8 @@ impl Plugin for EffectSuperSonicPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (spawn_super_sonics, despawn_super_sonics).run_if(in_state(GameState::Gaming)),
+            (spawn_super_sonics, despawn_super_sonics).in_set(GameplaySet),
         )
-        .add_event::<SpawnSuperSonic>();
+        .add_tick_event::<SpawnSuperSonic>();
     }
 }
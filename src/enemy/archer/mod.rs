@@ -1,36 +1,33 @@
 mod movement;
+mod pattern;
 mod shooting;
-mod spawn;
 
 use std::time::Duration;
 
 use bevy::prelude::*;
 use bevy_trickfilm::prelude::*;
 
-use crate::{GameAssets, GameState};
+use crate::{world::determinism::TickSet, GameplaySet};
 
-const SCORE: u32 = 200;
-const MOVE_SPEED: f32 = 80.0;
-const SHOOT_RANGE: f32 = 500.0;
-/// How long should the archer idle after shooting before moving again
-const MOVING_COOLDOWN: f32 = 2.0;
+use super::EnemyAnimations;
 
 pub use super::Enemy;
+pub use pattern::ProjectilePattern;
+pub use shooting::{spawn_archer_projectile, ArcherProjectile};
 
 pub struct EnemyArcherPlugin;
 
 impl Plugin for EnemyArcherPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
-            spawn::EnemyArcherSpawnPlugin,
             movement::EnemyArcherMovementPlugin,
             shooting::EnemyArcherShootingPlugin,
         ))
-        .add_systems(Update, (tick_cooldowns,))
         .add_systems(
-            PostUpdate,
-            (update_animations,).run_if(in_state(GameState::Gaming)),
-        );
+            FixedUpdate,
+            (tick_cooldowns, trigger_stunned).in_set(GameplaySet),
+        )
+        .add_systems(FixedUpdate, (update_animations,).in_set(TickSet::Late));
     }
 }
 
@@ -46,30 +43,41 @@ enum ArcherState {
 #[derive(Component)]
 pub struct EnemyArcher {
     state: ArcherState,
+    move_speed: f32,
+    shoot_range: f32,
+    /// How long the archer idles after shooting before moving again.
     moving_cooldown: Timer,
+    pattern: ProjectilePattern,
 }
 
-impl Default for EnemyArcher {
-    fn default() -> Self {
-        let mut moving_cooldown = Timer::from_seconds(MOVING_COOLDOWN, TimerMode::Once);
-        moving_cooldown.set_elapsed(Duration::from_secs_f32(MOVING_COOLDOWN));
+impl EnemyArcher {
+    pub fn new(
+        move_speed: f32,
+        shoot_range: f32,
+        moving_cooldown: f32,
+        pattern: ProjectilePattern,
+    ) -> Self {
+        let mut timer = Timer::from_seconds(moving_cooldown, TimerMode::Once);
+        timer.set_elapsed(Duration::from_secs_f32(moving_cooldown));
         Self {
             state: ArcherState::default(),
-            moving_cooldown,
+            move_speed,
+            shoot_range,
+            moving_cooldown: timer,
+            pattern,
         }
     }
 }
 
 fn update_animations(
-    assets: Res<GameAssets>,
-    mut q_archers: Query<(&EnemyArcher, &mut AnimationPlayer2D)>,
+    mut q_archers: Query<(&EnemyArcher, &EnemyAnimations, &mut AnimationPlayer2D)>,
 ) {
-    for (archer, mut animator) in &mut q_archers {
+    for (archer, animations, mut animator) in &mut q_archers {
         let animation = match archer.state {
-            ArcherState::Idling => assets.enemy_archer_animations[0].clone(),
-            ArcherState::Moving => assets.enemy_archer_animations[1].clone(),
-            ArcherState::Shooting => assets.enemy_archer_animations[2].clone(),
-            ArcherState::Stunned => assets.enemy_archer_animations[3].clone(),
+            ArcherState::Idling => animations.idle.clone(),
+            ArcherState::Moving => animations.moving.clone(),
+            ArcherState::Shooting => animations.attacking.clone(),
+            ArcherState::Stunned => animations.stunned.clone(),
         };
 
         animator.play(animation).repeat();
@@ -81,3 +89,11 @@ fn tick_cooldowns(time: Res<Time>, mut q_archers: Query<&mut EnemyArcher>) {
         archer.moving_cooldown.tick(time.delta());
     }
 }
+
+fn trigger_stunned(mut q_archers: Query<(&Enemy, &mut EnemyArcher)>) {
+    for (enemy, mut archer) in &mut q_archers {
+        if enemy.disabled || enemy.stunned {
+            archer.state = ArcherState::Stunned;
+        }
+    }
+}
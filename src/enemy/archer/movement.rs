@@ -1,8 +1,12 @@
 use bevy::prelude::*;
 
-use crate::player::Player;
+use crate::{
+    enemy::{steer, EnemySteeringSet, SpatialGrid, SpatialGridSet, Steering},
+    player::Player,
+    GameplaySet,
+};
 
-use super::{ArcherState, EnemyArcher, MOVE_SPEED};
+use super::{ArcherState, EnemyArcher};
 
 fn trigger_moving(mut q_archers: Query<&mut EnemyArcher>) {
     for mut archer in &mut q_archers {
@@ -18,24 +22,29 @@ fn trigger_moving(mut q_archers: Query<&mut EnemyArcher>) {
 
 fn move_archers(
     time: Res<Time>,
+    grid: Res<SpatialGrid>,
     q_player: Query<&Transform, With<Player>>,
-    mut q_enemies: Query<(&mut Transform, &EnemyArcher), Without<Player>>,
+    mut q_enemies: Query<(&mut Transform, &EnemyArcher, Option<&Steering>), Without<Player>>,
 ) {
     let player_pos = match q_player.get_single() {
         Ok(r) => r.translation,
         Err(_) => return,
     };
 
-    for (mut transform, archer) in &mut q_enemies {
+    for entry in grid.iter() {
+        let (mut transform, archer, steering) = match q_enemies.get_mut(entry.entity) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
         if archer.state != ArcherState::Moving {
             continue;
         }
 
         let dir = (player_pos - transform.translation)
             .truncate()
-            .normalize_or_zero()
-            .extend(0.0);
-        transform.translation += dir * MOVE_SPEED * time.delta_seconds();
+            .normalize_or_zero();
+        let dir = steer(steering, dir).extend(0.0);
+        transform.translation += dir * archer.move_speed * time.delta_seconds();
     }
 }
 
@@ -43,6 +52,9 @@ pub struct EnemyArcherMovementPlugin;
 
 impl Plugin for EnemyArcherMovementPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (trigger_moving, move_archers));
+        app.add_systems(
+            FixedUpdate,
+            (trigger_moving, move_archers.after(EnemySteeringSet)).in_set(GameplaySet),
+        );
     }
 }
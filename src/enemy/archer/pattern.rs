@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::GameAssets;
+
+use super::shooting::{spawn_projectile, ArcherProjectile};
+
+const HOMING_SPEED: f32 = 300.0;
+const FAST_SPEED: f32 = 1100.0;
+
+/// How an archer shoots.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ProjectilePattern {
+    /// One arrow straight at the player.
+    #[default]
+    Single,
+    /// `count` arrows fanned out evenly over `angle` degrees.
+    Spread { count: u32, angle: f32 },
+    /// A slow arrow that turns towards the player by up to `turn_rate` degrees per second.
+    Homing { turn_rate: f32 },
+    /// A fast arrow that is hard to dodge, its reflection is just as fast.
+    Fast,
+}
+
+impl ProjectilePattern {
+    /// Shoot the pattern from `pos` centered on the direction `rot`.
+    pub fn fire(&self, commands: &mut Commands, assets: &GameAssets, pos: Vec3, rot: Quat) {
+        match *self {
+            ProjectilePattern::Single => {
+                spawn_projectile(commands, assets, pos, rot, ArcherProjectile::default());
+            }
+            ProjectilePattern::Spread { count, angle } => {
+                let count = count.max(1);
+                let step = if count > 1 {
+                    angle.to_radians() / (count - 1) as f32
+                } else {
+                    0.0
+                };
+                let start = -step * (count - 1) as f32 / 2.0;
+                for i in 0..count {
+                    let rot = Quat::from_rotation_z(start + step * i as f32) * rot;
+                    spawn_projectile(commands, assets, pos, rot, ArcherProjectile::default());
+                }
+            }
+            ProjectilePattern::Homing { turn_rate } => {
+                let projectile = ArcherProjectile::new(HOMING_SPEED, turn_rate.to_radians());
+                spawn_projectile(commands, assets, pos, rot, projectile);
+            }
+            ProjectilePattern::Fast => {
+                let projectile = ArcherProjectile::new(FAST_SPEED, 0.0);
+                spawn_projectile(commands, assets, pos, rot, projectile);
+            }
+        }
+    }
+}
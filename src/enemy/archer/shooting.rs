@@ -3,17 +3,49 @@ use bevy_rapier2d::prelude::*;
 use bevy_trickfilm::prelude::*;
 
 use crate::{
-    enemy::EnemyProjectile, player::Player, utils::quat_from_vec3, world::camera::YSort,
-    GameAssets, GameState,
+    enemy::EnemyProjectile,
+    player::Player,
+    utils::{quat_from_vec3, turn_towards},
+    world::camera::YSort,
+    GameAssets, GameplaySet,
 };
 
-use super::{update_animations, ArcherState, EnemyArcher, SHOOT_RANGE};
+use super::{update_animations, ArcherState, EnemyArcher};
 
 const PROJECTILE_SPEED: f32 = 650.0;
 const OFFSET: f32 = 10.0;
+/// Homing arrows stop turning after this many seconds so that they can't circle forever.
+const HOMING_DURATION: f32 = 4.0;
+
+/// How an arrow flies, reflections of the arrow keep these properties.
+#[derive(Component, Clone, Copy)]
+pub struct ArcherProjectile {
+    pub speed: f32,
+    /// How fast the arrow turns towards its target in radians per second, `0.0` flies straight.
+    pub turn_rate: f32,
+    lifetime: f32,
+}
+
+impl ArcherProjectile {
+    pub fn new(speed: f32, turn_rate: f32) -> Self {
+        Self {
+            speed,
+            turn_rate,
+            lifetime: 0.0,
+        }
+    }
+
+    /// The speed relative to a normal arrow.
+    pub fn speed_multiplier(&self) -> f32 {
+        self.speed / PROJECTILE_SPEED
+    }
+}
 
-#[derive(Component)]
-struct Projectile;
+impl Default for ArcherProjectile {
+    fn default() -> Self {
+        Self::new(PROJECTILE_SPEED, 0.0)
+    }
+}
 
 fn trigger_shooting(
     q_player: Query<&Transform, With<Player>>,
@@ -32,7 +64,7 @@ fn trigger_shooting(
             continue;
         }
 
-        if archer_transform.translation.distance_squared(player_pos) <= SHOOT_RANGE.powi(2) {
+        if archer_transform.translation.distance_squared(player_pos) <= archer.shoot_range.powi(2) {
             archer.state = ArcherState::Shooting;
         }
     }
@@ -55,50 +87,88 @@ fn spawn_projectiles(
         }
 
         let rot = quat_from_vec3(player_pos - archer_transform.translation);
-        let transform = Transform::from_translation(
+        archer.pattern.fire(
+            &mut commands,
+            &assets,
             archer_transform.translation + rot.mul_vec3(Vec3::X) * OFFSET,
-        )
-        .with_rotation(rot)
-        .with_scale(Vec3::splat(2.0));
-
-        let collider = commands
-            .spawn((
-                Sensor,
-                ActiveEvents::COLLISION_EVENTS,
-                Collider::cuboid(25.0, 6.0),
-                CollisionGroups::default(),
-                TransformBundle::from_transform(Transform::from_translation(Vec3::new(
-                    0.0, 0.0, 0.0,
-                ))),
-            ))
-            .id();
-
-        let mut animator = AnimationPlayer2D::default();
-        animator.play(assets.archer_projectile_animations[0].clone());
-
-        commands
-            .spawn((
-                EnemyProjectile::default(),
-                Projectile,
-                YSort(1.0),
-                animator,
-                SpriteSheetBundle {
-                    transform,
-                    texture_atlas: assets.archer_projectile.clone(),
-                    ..default()
-                },
-            ))
-            .push_children(&[collider]);
+            rot,
+        );
 
         archer.moving_cooldown.reset();
         archer.state = ArcherState::Idling;
     }
 }
 
-fn move_projectiles(time: Res<Time>, mut q_projectiles: Query<&mut Transform, With<Projectile>>) {
-    for mut transform in &mut q_projectiles {
+pub fn spawn_archer_projectile(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    pos: Vec3,
+    rot: Quat,
+) -> Entity {
+    spawn_projectile(commands, assets, pos, rot, ArcherProjectile::default())
+}
+
+pub fn spawn_projectile(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    pos: Vec3,
+    rot: Quat,
+    projectile: ArcherProjectile,
+) -> Entity {
+    let transform = Transform::from_translation(pos)
+        .with_rotation(rot)
+        .with_scale(Vec3::splat(2.0));
+
+    let collider = commands
+        .spawn((
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            Collider::cuboid(25.0, 6.0),
+            CollisionGroups::default(),
+            TransformBundle::from_transform(Transform::from_translation(Vec3::new(0.0, 0.0, 0.0))),
+        ))
+        .id();
+
+    let mut animator = AnimationPlayer2D::default();
+    animator.play(assets.archer_projectile_animations[0].clone());
+
+    commands
+        .spawn((
+            EnemyProjectile::default(),
+            projectile,
+            YSort(1.0),
+            animator,
+            SpriteSheetBundle {
+                transform,
+                texture_atlas: assets.archer_projectile.clone(),
+                ..default()
+            },
+        ))
+        .push_children(&[collider])
+        .id()
+}
+
+fn move_projectiles(
+    time: Res<Time>,
+    q_player: Query<&Transform, With<Player>>,
+    mut q_projectiles: Query<(&mut Transform, &mut ArcherProjectile), Without<Player>>,
+) {
+    let player_pos = q_player.get_single().ok().map(|r| r.translation);
+
+    for (mut transform, mut projectile) in &mut q_projectiles {
+        projectile.lifetime += time.delta_seconds();
+        if let Some(player_pos) = player_pos {
+            if projectile.turn_rate > 0.0 && projectile.lifetime < HOMING_DURATION {
+                transform.rotation = turn_towards(
+                    transform.rotation,
+                    (player_pos - transform.translation).truncate(),
+                    projectile.turn_rate * time.delta_seconds(),
+                );
+            }
+        }
+
         let dir = transform.local_x();
-        transform.translation += dir * PROJECTILE_SPEED * time.delta_seconds();
+        transform.translation += dir * projectile.speed * time.delta_seconds();
     }
 }
 
@@ -107,13 +177,13 @@ pub struct EnemyArcherShootingPlugin;
 impl Plugin for EnemyArcherShootingPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
+            FixedUpdate,
             (
                 spawn_projectiles.before(trigger_shooting),
                 trigger_shooting.before(update_animations),
                 move_projectiles,
             )
-                .run_if(in_state(GameState::Gaming)),
+                .in_set(GameplaySet),
         );
     }
 }
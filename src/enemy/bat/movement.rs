@@ -1,29 +1,39 @@
 use bevy::prelude::*;
 
-use crate::player::Player;
+use crate::{
+    enemy::{steer, EnemySteeringSet, SpatialGrid, SpatialGridSet, Steering},
+    player::Player,
+    GameplaySet,
+};
 
-use super::{Enemy, EnemyBat, MOVE_SPEED};
+use super::{Enemy, EnemyBat};
 
 fn move_enemies(
     time: Res<Time>,
+    grid: Res<SpatialGrid>,
     q_player: Query<&Transform, With<Player>>,
-    mut q_enemies: Query<(&mut Transform, &Enemy), (With<EnemyBat>, Without<Player>)>,
+    mut q_enemies: Query<(&mut Transform, &Enemy, &EnemyBat, Option<&Steering>), Without<Player>>,
 ) {
     let player_pos = match q_player.get_single() {
         Ok(r) => r.translation,
         Err(_) => return,
     };
 
-    for (mut transform, enemy) in &mut q_enemies {
-        if enemy.disabled || enemy.stunned {
+    // Disabled enemies aren't in the grid.
+    for entry in grid.iter() {
+        let (mut transform, enemy, bat, steering) = match q_enemies.get_mut(entry.entity) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        if enemy.stunned {
             continue;
         }
 
         let dir = (player_pos - transform.translation)
             .truncate()
-            .normalize_or_zero()
-            .extend(0.0);
-        transform.translation += dir * MOVE_SPEED * time.delta_seconds();
+            .normalize_or_zero();
+        let dir = steer(steering, dir).extend(0.0);
+        transform.translation += dir * bat.move_speed * time.delta_seconds();
     }
 }
 
@@ -31,6 +41,9 @@ pub struct EnemyBatMovementPlugin;
 
 impl Plugin for EnemyBatMovementPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (move_enemies,));
+        app.add_systems(
+            FixedUpdate,
+            (move_enemies.after(EnemySteeringSet),).in_set(GameplaySet),
+        );
     }
 }
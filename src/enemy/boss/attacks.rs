@@ -0,0 +1,98 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::{
+    enemy::{spawn_archer_projectile, spawn_enemy, EnemyRoster},
+    utils::quat_from_vec2,
+    GameAssets, GameplaySet,
+};
+
+use super::{tick_phases, Boss, BossPhase, Enemy};
+
+/// How far from the center of the boss the arrows of a volley spawn.
+const VOLLEY_OFFSET: f32 = 50.0;
+/// The radius of the ring that summoned enemies spawn in.
+const SWARM_RADIUS: f32 = 80.0;
+
+fn shoot_volleys(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut q_bosses: Query<(&Transform, &Enemy, &mut Boss)>,
+) {
+    for (transform, enemy, mut boss) in &mut q_bosses {
+        if enemy.disabled || boss.phase != BossPhase::Volley || !boss.attack_timer.just_finished() {
+            continue;
+        }
+
+        // Every other volley is rotated by half a step so that the gaps don't stay in place.
+        let step = TAU / boss.volley_arrows.max(1) as f32;
+        let offset = if boss.volleys % 2 == 0 {
+            0.0
+        } else {
+            step / 2.0
+        };
+        for i in 0..boss.volley_arrows {
+            let dir = Vec2::from_angle(offset + step * i as f32);
+            spawn_archer_projectile(
+                &mut commands,
+                &assets,
+                transform.translation + dir.extend(0.0) * VOLLEY_OFFSET,
+                quat_from_vec2(dir),
+            );
+        }
+        boss.volleys += 1;
+    }
+}
+
+fn summon_swarms(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    rosters: Res<Assets<EnemyRoster>>,
+    q_bosses: Query<(&Transform, &Enemy, &Boss)>,
+) {
+    let roster = match rosters.get(&assets.enemy_roster) {
+        Some(r) => r,
+        None => return,
+    };
+
+    for (transform, enemy, boss) in &q_bosses {
+        if enemy.disabled || boss.phase != BossPhase::Swarm || !boss.attack_timer.just_finished() {
+            continue;
+        }
+
+        let definition = match roster.get(&boss.swarm_enemy) {
+            Some(r) => r,
+            None => {
+                error!(
+                    "failed to summon swarm, no enemy called {}",
+                    boss.swarm_enemy
+                );
+                continue;
+            }
+        };
+
+        let step = TAU / boss.swarm_size.max(1) as f32;
+        for i in 0..boss.swarm_size {
+            let dir = Vec2::from_angle(step * i as f32);
+            spawn_enemy(
+                &mut commands,
+                definition,
+                transform.translation + dir.extend(0.0) * SWARM_RADIUS,
+            );
+        }
+    }
+}
+
+pub struct EnemyBossAttacksPlugin;
+
+impl Plugin for EnemyBossAttacksPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (shoot_volleys, summon_swarms)
+                .after(tick_phases)
+                .in_set(GameplaySet),
+        );
+    }
+}
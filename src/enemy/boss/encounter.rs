@@ -0,0 +1,96 @@
+use std::f32::consts::TAU;
+
+use rand::Rng;
+
+use bevy::prelude::*;
+
+use crate::{
+    enemy::{enemy_spawning_active, spawn_enemy, EnemyRoster},
+    player::{kill_counter::KillCounter, speed_timer::SpeedTimer, Player},
+    world::seed::GameRng,
+    GameAssets, GameState, GameplaySet,
+};
+
+use super::Boss;
+
+/// Bosses are slow, so they spawn a lot closer than normal enemies.
+const BOSS_SPAWN_OFFSET: f32 = 450.0;
+
+/// Counts the kills and time since the last boss died.
+#[derive(Resource, Default)]
+struct BossEncounter {
+    last_kills: u32,
+    last_time: f32,
+}
+
+pub fn boss_alive(q_bosses: Query<(), With<Boss>>) -> bool {
+    !q_bosses.is_empty()
+}
+
+fn start_encounters(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    rosters: Res<Assets<EnemyRoster>>,
+    speed_timer: Res<SpeedTimer>,
+    kill_counter: Res<KillCounter>,
+    mut game_rng: ResMut<GameRng>,
+    mut encounter: ResMut<BossEncounter>,
+    q_player: Query<&Transform, With<Player>>,
+    q_bosses: Query<(), With<Boss>>,
+) {
+    let player_transform = match q_player.get_single() {
+        Ok(r) => r,
+        Err(_) => return,
+    };
+    let roster = match rosters.get(&assets.enemy_roster) {
+        Some(r) => r,
+        None => return,
+    };
+    let schedule = match &roster.director.boss {
+        Some(r) => r,
+        None => return,
+    };
+
+    // The next encounter only counts from the moment the boss is dead.
+    if !q_bosses.is_empty() {
+        encounter.last_kills = kill_counter.kills();
+        encounter.last_time = speed_timer.elapsed;
+        return;
+    }
+
+    let kills = kill_counter.kills().saturating_sub(encounter.last_kills);
+    let time = speed_timer.elapsed - encounter.last_time;
+    if kills < schedule.every_kills && time < schedule.every_seconds {
+        return;
+    }
+
+    let definition = match roster.get(&schedule.enemy) {
+        Some(r) => r,
+        None => return,
+    };
+    let pos = player_transform.translation
+        + Quat::from_rotation_z(game_rng.gen_range(0.0..TAU)).mul_vec3(Vec3::X) * BOSS_SPAWN_OFFSET;
+    spawn_enemy(&mut commands, definition, pos);
+
+    encounter.last_kills = kill_counter.kills();
+    encounter.last_time = speed_timer.elapsed;
+}
+
+fn reset_encounter(mut encounter: ResMut<BossEncounter>) {
+    *encounter = BossEncounter::default();
+}
+
+pub struct EnemyBossEncounterPlugin;
+
+impl Plugin for EnemyBossEncounterPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BossEncounter>()
+            .add_systems(
+                FixedUpdate,
+                (start_encounters,)
+                    .in_set(GameplaySet)
+                    .run_if(enemy_spawning_active),
+            )
+            .add_systems(OnEnter(GameState::Restart), reset_encounter);
+    }
+}
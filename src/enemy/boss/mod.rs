@@ -0,0 +1,146 @@
+mod attacks;
+mod encounter;
+mod movement;
+
+use bevy::prelude::*;
+use bevy_trickfilm::prelude::*;
+
+use crate::{world::determinism::TickSet, GameplaySet};
+
+use super::{Enemy, EnemyAnimations};
+
+pub use encounter::boss_alive;
+
+pub struct EnemyBossPlugin;
+
+impl Plugin for EnemyBossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            attacks::EnemyBossAttacksPlugin,
+            encounter::EnemyBossEncounterPlugin,
+            movement::EnemyBossMovementPlugin,
+        ))
+        .add_systems(FixedUpdate, (tick_phases,).in_set(GameplaySet))
+        .add_systems(FixedUpdate, (update_animations,).in_set(TickSet::Late));
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+enum BossPhase {
+    #[default]
+    Volley,
+    Swarm,
+    /// The weak point is open, any hit kills the boss.
+    Exposed,
+}
+
+#[derive(Component)]
+pub struct Boss {
+    phase: BossPhase,
+    /// The phase to go back to once the weak point closes.
+    resume_phase: BossPhase,
+    move_speed: f32,
+    phase_timer: Timer,
+    attack_timer: Timer,
+    volley_interval: f32,
+    volley_arrows: u32,
+    volleys: u32,
+    swarm_interval: f32,
+    swarm_size: u32,
+    swarm_enemy: String,
+    weak_point: Timer,
+}
+
+impl Boss {
+    pub fn new(
+        move_speed: f32,
+        phase_duration: f32,
+        volley_interval: f32,
+        volley_arrows: u32,
+        swarm_interval: f32,
+        swarm_size: u32,
+        swarm_enemy: String,
+        weak_point_duration: f32,
+    ) -> Self {
+        Self {
+            phase: BossPhase::default(),
+            resume_phase: BossPhase::default(),
+            move_speed,
+            phase_timer: Timer::from_seconds(phase_duration, TimerMode::Repeating),
+            attack_timer: Timer::from_seconds(volley_interval, TimerMode::Repeating),
+            volley_interval,
+            volley_arrows,
+            volleys: 0,
+            swarm_interval,
+            swarm_size,
+            swarm_enemy,
+            weak_point: Timer::from_seconds(weak_point_duration, TimerMode::Once),
+        }
+    }
+
+    /// Whether the weak point is open.
+    pub fn exposed(&self) -> bool {
+        self.phase == BossPhase::Exposed
+    }
+
+    fn enter_phase(&mut self, phase: BossPhase) {
+        let interval = match phase {
+            BossPhase::Volley => self.volley_interval,
+            BossPhase::Swarm => self.swarm_interval,
+            BossPhase::Exposed => {
+                self.resume_phase = self.phase;
+                self.weak_point.reset();
+                self.phase = phase;
+                return;
+            }
+        };
+        self.attack_timer = Timer::from_seconds(interval, TimerMode::Repeating);
+        self.phase = phase;
+    }
+}
+
+/// The hook stuns the boss which opens its weak point for a while.
+fn tick_phases(time: Res<Time>, mut q_bosses: Query<(&mut Enemy, &mut Boss)>) {
+    for (mut enemy, mut boss) in &mut q_bosses {
+        if enemy.disabled {
+            continue;
+        }
+
+        if boss.phase == BossPhase::Exposed {
+            boss.weak_point.tick(time.delta());
+            if boss.weak_point.finished() {
+                enemy.stunned = false;
+                let phase = boss.resume_phase;
+                boss.enter_phase(phase);
+            }
+            continue;
+        }
+
+        if enemy.stunned {
+            boss.enter_phase(BossPhase::Exposed);
+            continue;
+        }
+
+        boss.attack_timer.tick(time.delta());
+        boss.phase_timer.tick(time.delta());
+        if boss.phase_timer.just_finished() {
+            let phase = match boss.phase {
+                BossPhase::Volley => BossPhase::Swarm,
+                _ => BossPhase::Volley,
+            };
+            boss.enter_phase(phase);
+        }
+    }
+}
+
+fn update_animations(mut q_bosses: Query<(&Boss, &EnemyAnimations, &mut AnimationPlayer2D)>) {
+    for (boss, animations, mut animator) in &mut q_bosses {
+        let animation = match boss.phase {
+            BossPhase::Volley => animations.attacking.clone(),
+            BossPhase::Swarm => animations.moving.clone(),
+            BossPhase::Exposed => animations.stunned.clone(),
+        };
+
+        animator.play(animation).repeat();
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::{player::Player, GameplaySet};
+
+use super::{Boss, Enemy};
+
+fn follow_player(
+    time: Res<Time>,
+    q_player: Query<&Transform, With<Player>>,
+    mut q_bosses: Query<(&mut Transform, &Enemy, &Boss), Without<Player>>,
+) {
+    let player_pos = match q_player.get_single() {
+        Ok(r) => r.translation,
+        Err(_) => return,
+    };
+
+    for (mut transform, enemy, boss) in &mut q_bosses {
+        if enemy.disabled || boss.exposed() {
+            continue;
+        }
+
+        let dir = (player_pos - transform.translation)
+            .truncate()
+            .normalize_or_zero()
+            .extend(0.0);
+        transform.translation += dir * boss.move_speed * time.delta_seconds();
+    }
+}
+
+pub struct EnemyBossMovementPlugin;
+
+impl Plugin for EnemyBossMovementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, (follow_player,).in_set(GameplaySet));
+    }
+}
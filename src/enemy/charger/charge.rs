@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    enemy::{EnemyVulnerability, HitSource},
+    GameplaySet,
+};
+
+use super::{ChargerState, Enemy, EnemyCharger};
+
+/// Kill every enemy that a charging charger runs into.
+/// Enemy colliders don't have rigid bodies, so rapier doesn't report collisions
+/// between them and we have to query for the intersections ourselves.
+fn charge_through_enemies(
+    rapier_context: Res<RapierContext>,
+    q_chargers: Query<(Entity, &EnemyCharger, &Children)>,
+    q_colliders: Query<(&Collider, &GlobalTransform)>,
+    q_parents: Query<&Parent, With<Collider>>,
+    mut q_enemies: Query<&mut Enemy>,
+    mut vulnerability: EnemyVulnerability,
+) {
+    let mut victims = Vec::new();
+    for (entity, charger, children) in &q_chargers {
+        if charger.state != ChargerState::Charging {
+            continue;
+        }
+
+        for child in children {
+            let (collider, transform) = match q_colliders.get(*child) {
+                Ok(r) => r,
+                Err(_) => continue,
+            };
+
+            rapier_context.intersections_with_shape(
+                transform.translation().truncate(),
+                0.0,
+                collider,
+                QueryFilter::default().exclude_collider(*child),
+                |hit| {
+                    if let Ok(parent) = q_parents.get(hit) {
+                        if parent.get() != entity {
+                            victims.push((parent.get(), transform.translation().truncate()));
+                        }
+                    }
+                    true
+                },
+            );
+        }
+    }
+
+    for (victim, charger_pos) in victims {
+        let mut enemy = match q_enemies.get_mut(victim) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        // Already killed this tick, most likely by the player.
+        if enemy.disabled {
+            continue;
+        }
+        if vulnerability.hit(victim, HitSource::Charge, charger_pos) {
+            enemy.disabled = true;
+            enemy.killed_by = Some(HitSource::Charge);
+        }
+    }
+}
+
+pub struct EnemyChargerChargePlugin;
+
+impl Plugin for EnemyChargerChargePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, (charge_through_enemies,).in_set(GameplaySet));
+    }
+}
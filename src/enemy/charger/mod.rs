@@ -0,0 +1,161 @@
+mod charge;
+mod movement;
+
+use bevy::prelude::*;
+use bevy_trickfilm::prelude::*;
+
+use crate::{player::Player, world::determinism::TickSet, GameplaySet};
+
+use super::EnemyAnimations;
+
+pub use super::Enemy;
+
+/// How fast the charger flashes while winding up, in flashes per second.
+const TELEGRAPH_FREQUENCY: f32 = 8.0;
+const TELEGRAPH_COLOR: Color = Color::rgb(1.0, 0.2, 0.2);
+
+pub struct EnemyChargerPlugin;
+
+impl Plugin for EnemyChargerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            movement::EnemyChargerMovementPlugin,
+            charge::EnemyChargerChargePlugin,
+        ))
+        .add_systems(
+            FixedUpdate,
+            (tick_states, trigger_stunned, update_hook_immunity)
+                .chain()
+                .in_set(GameplaySet),
+        )
+        .add_systems(
+            FixedUpdate,
+            (update_animations, telegraph_wind_up).in_set(TickSet::Late),
+        );
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+enum ChargerState {
+    #[default]
+    Approaching,
+    WindingUp,
+    Charging,
+    Recovering,
+    Stunned,
+}
+
+#[derive(Component)]
+pub struct EnemyCharger {
+    state: ChargerState,
+    move_speed: f32,
+    tint: Color,
+    /// The charger starts winding up once the player is this close.
+    charge_range: f32,
+    charge_speed: f32,
+    charge_dir: Vec2,
+    wind_up: Timer,
+    charge: Timer,
+    recovery: Timer,
+}
+
+impl EnemyCharger {
+    pub fn new(
+        move_speed: f32,
+        tint: Color,
+        charge_range: f32,
+        charge_speed: f32,
+        wind_up: f32,
+        charge_duration: f32,
+        recovery: f32,
+    ) -> Self {
+        Self {
+            state: ChargerState::default(),
+            move_speed,
+            tint,
+            charge_range,
+            charge_speed,
+            charge_dir: Vec2::ZERO,
+            wind_up: Timer::from_seconds(wind_up, TimerMode::Once),
+            charge: Timer::from_seconds(charge_duration, TimerMode::Once),
+            recovery: Timer::from_seconds(recovery, TimerMode::Once),
+        }
+    }
+}
+
+fn tick_states(
+    time: Res<Time>,
+    q_player: Query<&Transform, With<Player>>,
+    mut q_chargers: Query<(&Transform, &mut EnemyCharger), Without<Player>>,
+) {
+    let player_pos = match q_player.get_single() {
+        Ok(r) => r.translation.truncate(),
+        Err(_) => return,
+    };
+
+    for (transform, mut charger) in &mut q_chargers {
+        match charger.state {
+            ChargerState::WindingUp => {
+                charger.wind_up.tick(time.delta());
+                if charger.wind_up.finished() {
+                    // Aim only now so that the player has to react to the telegraph.
+                    charger.charge_dir =
+                        (player_pos - transform.translation.truncate()).normalize_or_zero();
+                    charger.charge.reset();
+                    charger.state = ChargerState::Charging;
+                }
+            }
+            ChargerState::Charging => {
+                charger.charge.tick(time.delta());
+                if charger.charge.finished() {
+                    charger.recovery.reset();
+                    charger.state = ChargerState::Recovering;
+                }
+            }
+            ChargerState::Recovering => {
+                charger.recovery.tick(time.delta());
+                if charger.recovery.finished() {
+                    charger.state = ChargerState::Approaching;
+                }
+            }
+            ChargerState::Approaching | ChargerState::Stunned => {}
+        }
+    }
+}
+
+fn trigger_stunned(mut q_chargers: Query<(&Enemy, &mut EnemyCharger)>) {
+    for (enemy, mut charger) in &mut q_chargers {
+        if enemy.disabled || enemy.stunned {
+            charger.state = ChargerState::Stunned;
+        }
+    }
+}
+
+/// Chargers can only be hooked while they recover from a charge.
+fn update_hook_immunity(mut q_chargers: Query<(&mut Enemy, &EnemyCharger)>) {
+    for (mut enemy, charger) in &mut q_chargers {
+        enemy.hook_immune = charger.state != ChargerState::Recovering;
+    }
+}
+
+fn update_animations(
+    mut q_chargers: Query<(&EnemyCharger, &EnemyAnimations, &mut AnimationPlayer2D)>,
+) {
+    for (charger, animations, mut animator) in &mut q_chargers {
+        let animation = match charger.state {
+            ChargerState::Approaching | ChargerState::Charging => animations.moving.clone(),
+            ChargerState::WindingUp => animations.attacking.clone(),
+            ChargerState::Recovering | ChargerState::Stunned => animations.stunned.clone(),
+        };
+
+        animator.play(animation).repeat();
+    }
+}
+
+fn telegraph_wind_up(mut q_chargers: Query<(&EnemyCharger, &mut TextureAtlasSprite)>) {
+    for (charger, mut sprite) in &mut q_chargers {
+        let flash = charger.state == ChargerState::WindingUp
+            && (charger.wind_up.elapsed_secs() * TELEGRAPH_FREQUENCY).fract() < 0.5;
+        sprite.color = if flash { TELEGRAPH_COLOR } else { charger.tint };
+    }
+}
@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+
+use crate::{
+    enemy::{steer, EnemySteeringSet, SpatialGrid, SpatialGridSet, Steering},
+    player::Player,
+    GameplaySet,
+};
+
+use super::{tick_states, ChargerState, EnemyCharger};
+
+fn approach_player(
+    time: Res<Time>,
+    grid: Res<SpatialGrid>,
+    q_player: Query<&Transform, With<Player>>,
+    mut q_chargers: Query<(&mut Transform, &mut EnemyCharger, Option<&Steering>), Without<Player>>,
+) {
+    let player_pos = match q_player.get_single() {
+        Ok(r) => r.translation,
+        Err(_) => return,
+    };
+
+    for entry in grid.iter() {
+        let (mut transform, mut charger, steering) = match q_chargers.get_mut(entry.entity) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        if charger.state != ChargerState::Approaching {
+            continue;
+        }
+
+        if transform.translation.distance_squared(player_pos) <= charger.charge_range.powi(2) {
+            charger.wind_up.reset();
+            charger.state = ChargerState::WindingUp;
+            continue;
+        }
+
+        let dir = (player_pos - transform.translation)
+            .truncate()
+            .normalize_or_zero();
+        let dir = steer(steering, dir).extend(0.0);
+        transform.translation += dir * charger.move_speed * time.delta_seconds();
+    }
+}
+
+fn charge(
+    time: Res<Time>,
+    grid: Res<SpatialGrid>,
+    mut q_chargers: Query<(&mut Transform, &EnemyCharger)>,
+) {
+    for entry in grid.iter() {
+        let (mut transform, charger) = match q_chargers.get_mut(entry.entity) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        if charger.state != ChargerState::Charging {
+            continue;
+        }
+
+        transform.translation +=
+            charger.charge_dir.extend(0.0) * charger.charge_speed * time.delta_seconds();
+    }
+}
+
+pub struct EnemyChargerMovementPlugin;
+
+impl Plugin for EnemyChargerMovementPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                approach_player.before(tick_states).after(EnemySteeringSet),
+                charge.after(tick_states).after(SpatialGridSet),
+            )
+                .in_set(GameplaySet),
+        );
+    }
+}
@@ -1,262 +1,97 @@
 use bevy::prelude::*;
-use bevy_rapier2d::prelude::*;
 
 use crate::{
+    collision::{CollisionRouterSet, HitEnemy, ProjectileStruck},
     player::{
-        dash::DashLanding,
         reflection_projectile::{ReflectionProjectile, SpawnReflectionProjectile},
-        spawn::PlayerDashColliderContainer,
         strike::Strike,
     },
     utils::FixedRotation,
+    GameplaySet,
 };
 
-use super::{Enemy, EnemyProjectile, DASH_SCORE_MULTIPLIYER, REFLECTION_PROJECTILE_SCORE_ADDITION};
+use super::{
+    ArcherProjectile, Enemy, EnemyProjectile, EnemyVulnerability, HitSource,
+    DASH_SCORE_MULTIPLIYER, REFLECTION_PROJECTILE_SCORE_ADDITION,
+};
 
-fn player_strike_collisions(
-    q_strikes: Query<&Strike>,
+fn hit_enemies(
     mut q_enemies: Query<&mut Enemy>,
-    q_colliders: Query<&Parent, (With<Collider>, Without<Enemy>, Without<Strike>)>,
-    mut ev_collision_events: EventReader<CollisionEvent>,
+    q_strikes: Query<&Strike>,
+    mut q_reflection_projectiles: Query<&mut ReflectionProjectile>,
+    mut vulnerability: EnemyVulnerability,
+    mut ev_hit_enemy: EventReader<HitEnemy>,
 ) {
-    for ev in ev_collision_events.read() {
-        let (source, target) = match ev {
-            CollisionEvent::Started(source, target, _) => (source, target),
-            CollisionEvent::Stopped(_, _, _) => continue,
-        };
-
-        let source_parent = match q_colliders.get(*source) {
-            Ok(p) => p.get(),
+    for ev in ev_hit_enemy.read() {
+        let mut enemy = match q_enemies.get_mut(ev.enemy) {
+            Ok(r) => r,
             Err(_) => continue,
         };
-        let target_parent = match q_colliders.get(*target) {
-            Ok(p) => p.get(),
-            Err(_) => continue,
-        };
-
-        let mut enemy = if let Ok(r) = q_enemies.get_mut(source_parent) {
-            r
-        } else if let Ok(r) = q_enemies.get_mut(target_parent) {
-            r
-        } else {
-            continue;
-        };
 
-        let _ = if let Ok(r) = q_strikes.get(source_parent) {
-            r
-        } else if let Ok(r) = q_strikes.get(target_parent) {
-            r
-        } else {
+        if !vulnerability.hit(ev.enemy, ev.source, ev.pos) {
             continue;
-        };
+        }
 
         enemy.disabled = true;
+        enemy.killed_by = Some(ev.source);
+        match ev.source {
+            HitSource::Reflection => {
+                if let Ok(mut projectile) = q_reflection_projectiles.get_mut(ev.by) {
+                    projectile.increase_counter();
+                    enemy.score +=
+                        REFLECTION_PROJECTILE_SCORE_ADDITION * projectile.enemy_counter();
+                }
+            }
+            HitSource::Dash | HitSource::DashLanding => {
+                enemy.score = (enemy.score as f32 * DASH_SCORE_MULTIPLIYER) as u32;
+            }
+            HitSource::Strike => {
+                enemy.strike_index = q_strikes.get(ev.by).ok().map(|strike| strike.index);
+            }
+            HitSource::Hook | HitSource::Charge => {}
+        }
     }
 }
 
-fn projectile_strike_collisions(
-    q_strikes: Query<(&FixedRotation, &Strike)>,
-    mut q_enemy_projectiles: Query<(&Transform, &mut EnemyProjectile)>,
-    q_colliders: Query<&Parent, (With<Collider>, Without<EnemyProjectile>, Without<Strike>)>,
-    mut ev_collision_events: EventReader<CollisionEvent>,
+fn reflect_projectiles(
+    q_strikes: Query<&FixedRotation>,
+    mut q_enemy_projectiles: Query<(&Transform, &mut EnemyProjectile, Option<&ArcherProjectile>)>,
+    mut ev_projectile_struck: EventReader<ProjectileStruck>,
     mut ev_spawn_reflection_projectile: EventWriter<SpawnReflectionProjectile>,
 ) {
-    for ev in ev_collision_events.read() {
-        let (source, target) = match ev {
-            CollisionEvent::Started(source, target, _) => (source, target),
-            CollisionEvent::Stopped(_, _, _) => continue,
-        };
-
-        let source_parent = match q_colliders.get(*source) {
-            Ok(p) => p.get(),
-            Err(_) => continue,
-        };
-        let target_parent = match q_colliders.get(*target) {
-            Ok(p) => p.get(),
-            Err(_) => continue,
-        };
-
-        let (projectile_transform, mut enemy_projectile) =
-            if let Ok(r) = q_enemy_projectiles.get_mut(source_parent) {
-                r
-            } else if let Ok(r) = q_enemy_projectiles.get_mut(target_parent) {
-                r
-            } else {
-                continue;
+    for ev in ev_projectile_struck.read() {
+        let (projectile_transform, mut enemy_projectile, archer_projectile) =
+            match q_enemy_projectiles.get_mut(ev.projectile) {
+                Ok(r) => r,
+                Err(_) => continue,
             };
-
-        let (strike_fixed_rotation, _) = if let Ok(r) = q_strikes.get(source_parent) {
-            r
-        } else if let Ok(r) = q_strikes.get(target_parent) {
-            r
-        } else {
-            continue;
+        let strike_fixed_rotation = match q_strikes.get(ev.strike) {
+            Ok(r) => r,
+            Err(_) => continue,
         };
 
         let dir = strike_fixed_rotation.rot.mul_vec3(Vec3::X).truncate();
+        let archer_projectile = archer_projectile.copied().unwrap_or_default();
 
         enemy_projectile.disabled = true;
         ev_spawn_reflection_projectile.send(SpawnReflectionProjectile {
             pos: projectile_transform.translation.truncate(),
             dir,
+            speed_multiplier: archer_projectile.speed_multiplier(),
+            turn_rate: archer_projectile.turn_rate,
         })
     }
 }
 
-fn player_reflection_projectiles_collisions(
-    mut q_projectiles: Query<&mut ReflectionProjectile>,
-    mut q_enemies: Query<&mut Enemy>,
-    q_colliders: Query<
-        &Parent,
-        (
-            With<Collider>,
-            Without<Enemy>,
-            Without<ReflectionProjectile>,
-        ),
-    >,
-    mut ev_collision_events: EventReader<CollisionEvent>,
-) {
-    for ev in ev_collision_events.read() {
-        let (source, target) = match ev {
-            CollisionEvent::Started(source, target, _) => (source, target),
-            CollisionEvent::Stopped(_, _, _) => continue,
-        };
-
-        let source_parent = match q_colliders.get(*source) {
-            Ok(p) => p.get(),
-            Err(_) => continue,
-        };
-        let target_parent = match q_colliders.get(*target) {
-            Ok(p) => p.get(),
-            Err(_) => continue,
-        };
-
-        let mut enemy = if let Ok(r) = q_enemies.get_mut(source_parent) {
-            r
-        } else if let Ok(r) = q_enemies.get_mut(target_parent) {
-            r
-        } else {
-            continue;
-        };
-
-        let mut projectile = if let Ok(r) = q_projectiles.get_mut(source_parent) {
-            r
-        } else if let Ok(r) = q_projectiles.get_mut(target_parent) {
-            r
-        } else {
-            continue;
-        };
-
-        projectile.increase_counter();
-        enemy.disabled = true;
-        enemy.score += REFLECTION_PROJECTILE_SCORE_ADDITION * projectile.enemy_counter();
-    }
-}
-
-fn player_dash_collisions(
-    q_dash_collider_containers: Query<&PlayerDashColliderContainer>,
-    mut q_enemies: Query<&mut Enemy>,
-    q_colliders: Query<
-        &Parent,
-        (
-            With<Collider>,
-            Without<Enemy>,
-            Without<PlayerDashColliderContainer>,
-        ),
-    >,
-    mut ev_collision_events: EventReader<CollisionEvent>,
-) {
-    for ev in ev_collision_events.read() {
-        let (source, target) = match ev {
-            CollisionEvent::Started(source, target, _) => (source, target),
-            CollisionEvent::Stopped(_, _, _) => continue,
-        };
-
-        let source_parent = match q_colliders.get(*source) {
-            Ok(p) => p.get(),
-            Err(_) => continue,
-        };
-        let target_parent = match q_colliders.get(*target) {
-            Ok(p) => p.get(),
-            Err(_) => continue,
-        };
-
-        let mut enemy = if let Ok(r) = q_enemies.get_mut(source_parent) {
-            r
-        } else if let Ok(r) = q_enemies.get_mut(target_parent) {
-            r
-        } else {
-            continue;
-        };
-
-        let _ = if let Ok(r) = q_dash_collider_containers.get(source_parent) {
-            r
-        } else if let Ok(r) = q_dash_collider_containers.get(target_parent) {
-            r
-        } else {
-            continue;
-        };
-
-        enemy.disabled = true;
-        enemy.score = (enemy.score as f32 * DASH_SCORE_MULTIPLIYER) as u32;
-    }
-}
-
-fn player_dash_landing_collisions(
-    q_dash_landings: Query<&DashLanding>,
-    mut q_enemies: Query<&mut Enemy>,
-    q_colliders: Query<&Parent, (With<Collider>, Without<Enemy>, Without<DashLanding>)>,
-    mut ev_collision_events: EventReader<CollisionEvent>,
-) {
-    for ev in ev_collision_events.read() {
-        let (source, target) = match ev {
-            CollisionEvent::Started(source, target, _) => (source, target),
-            CollisionEvent::Stopped(_, _, _) => continue,
-        };
-
-        let source_parent = match q_colliders.get(*source) {
-            Ok(p) => p.get(),
-            Err(_) => continue,
-        };
-        let target_parent = match q_colliders.get(*target) {
-            Ok(p) => p.get(),
-            Err(_) => continue,
-        };
-
-        let mut enemy = if let Ok(r) = q_enemies.get_mut(source_parent) {
-            r
-        } else if let Ok(r) = q_enemies.get_mut(target_parent) {
-            r
-        } else {
-            continue;
-        };
-
-        let _ = if let Ok(r) = q_dash_landings.get(source_parent) {
-            r
-        } else if let Ok(r) = q_dash_landings.get(target_parent) {
-            r
-        } else {
-            continue;
-        };
-
-        enemy.disabled = true;
-        enemy.score = (enemy.score as f32 * DASH_SCORE_MULTIPLIYER) as u32;
-    }
-}
-
 pub struct EnemyCollisionPlugin;
 
 impl Plugin for EnemyCollisionPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
-            (
-                player_strike_collisions,
-                projectile_strike_collisions,
-                player_reflection_projectiles_collisions,
-                player_dash_collisions,
-                player_dash_landing_collisions,
-            ),
+            FixedUpdate,
+            (hit_enemies, reflect_projectiles)
+                .after(CollisionRouterSet)
+                .in_set(GameplaySet),
         );
     }
 }
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::{player::Player, world::camera::MainCamera, GameState};
+
+use super::{Enemy, SpatialGrid};
+
+/// Enemies this far outside of the view are still drawn so that they don't pop in.
+const VIEW_MARGIN: f32 = 64.0;
+
+/// Hide the enemies that are off-screen and face the visible ones towards the player.
+fn cull_enemies(
+    grid: Res<SpatialGrid>,
+    q_camera: Query<(&GlobalTransform, &OrthographicProjection), With<MainCamera>>,
+    q_player: Query<&Transform, With<Player>>,
+    mut q_enemies: Query<(&mut Visibility, &mut TextureAtlasSprite), With<Enemy>>,
+) {
+    let (camera_transform, projection) = match q_camera.get_single() {
+        Ok(r) => r,
+        Err(_) => return,
+    };
+    let player_pos = match q_player.get_single() {
+        Ok(r) => r.translation.truncate(),
+        Err(_) => return,
+    };
+
+    let center = camera_transform.translation().truncate() + projection.area.center();
+    let radius = projection.area.half_size().length() + VIEW_MARGIN;
+
+    for entry in grid.outside_radius(center, radius) {
+        if let Ok((mut visibility, _)) = q_enemies.get_mut(entry.entity) {
+            visibility.set_if_neq(Visibility::Hidden);
+        }
+    }
+
+    for entry in grid.in_radius(center, radius) {
+        let (mut visibility, mut sprite) = match q_enemies.get_mut(entry.entity) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+
+        visibility.set_if_neq(Visibility::Inherited);
+        let flip_x = player_pos.x < entry.pos.x;
+        if sprite.flip_x != flip_x {
+            sprite.flip_x = flip_x;
+        }
+    }
+}
+
+pub struct EnemyCullingPlugin;
+
+impl Plugin for EnemyCullingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (cull_enemies,).run_if(in_state(GameState::Gaming)));
+    }
+}
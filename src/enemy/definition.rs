@@ -0,0 +1,561 @@
+use std::fmt;
+
+use bevy::asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy::utils::BoxedFuture;
+use bevy_rapier2d::prelude::*;
+use bevy_trickfilm::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+use serde::{Deserialize, Deserializer};
+
+use super::{HitSource, ProjectilePattern};
+
+const DEFAULT_SCALE: f32 = 2.0;
+
+/// All enemy types and the difficulty settings of the director, loaded from `enemy/enemies.ron`.
+#[derive(Asset, TypePath)]
+pub struct EnemyRoster {
+    pub director: DirectorConfig,
+    pub enemies: Vec<EnemyDefinition>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct DirectorConfig {
+    /// Seconds per wave.
+    pub wave_duration: f32,
+    /// Enemies spawned at once at the start of every wave.
+    pub wave_burst: u32,
+    /// Over how many seconds the kill rate of the player is measured.
+    pub kill_rate_window: f32,
+    /// Extra enemies per second for every enemy per second the player kills.
+    pub kill_rate_factor: f32,
+    /// Sorted by time and linearly interpolated over the elapsed time of the run, never empty.
+    pub difficulty: Vec<DifficultyPoint>,
+    #[serde(default)]
+    pub boss: Option<BossSchedule>,
+}
+
+/// When boss encounters happen, normal spawning pauses while the boss is alive.
+#[derive(Deserialize, Clone)]
+pub struct BossSchedule {
+    /// The name of the boss in the roster.
+    pub enemy: String,
+    /// A boss shows up after this many kills since the last encounter...
+    pub every_kills: u32,
+    /// ...or after this many seconds since the last encounter, whatever comes first.
+    pub every_seconds: f32,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct DifficultyPoint {
+    pub time: f32,
+    /// Enemies per second.
+    pub spawn_rate: f32,
+    pub max_alive: u32,
+}
+
+pub struct EnemyDefinition {
+    pub name: String,
+    pub atlas: Handle<TextureAtlas>,
+    pub tint: Color,
+    pub scale: f32,
+    pub animations: EnemyAnimations,
+    pub shadow: Handle<Image>,
+    pub shadow_offset: Vec2,
+    pub collider: ColliderShape,
+    pub collider_offset: Vec2,
+    pub score: u32,
+    pub move_speed: f32,
+    pub behaviour: EnemyBehaviour,
+    pub on_death: Option<DeathBehaviour>,
+    pub shield: Option<ShieldDefinition>,
+    pub steering: Option<SteeringDefinition>,
+    /// How likely this enemy is picked relative to the others.
+    pub spawn_weight: f32,
+    /// Added to the `spawn_weight` for every wave after the `first_wave`.
+    pub weight_per_wave: f32,
+    /// The enemy doesn't spawn before this wave.
+    pub first_wave: u32,
+}
+
+/// The animations of an enemy, the optional ones in the file fall back to `idle`.
+#[derive(Component, Clone)]
+pub struct EnemyAnimations {
+    pub idle: Handle<AnimationClip2D>,
+    pub moving: Handle<AnimationClip2D>,
+    pub attacking: Handle<AnimationClip2D>,
+    pub stunned: Handle<AnimationClip2D>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+pub enum ColliderShape {
+    Ball { radius: f32 },
+    Capsule { a: Vec2, b: Vec2, radius: f32 },
+    Cuboid { half_x: f32, half_y: f32 },
+}
+
+/// Which systems drive the enemy.
+#[derive(Deserialize, Clone)]
+pub enum EnemyBehaviour {
+    /// Flies straight at the player.
+    Bat,
+    /// Walks towards the player and shoots once in range.
+    Archer {
+        shoot_range: f32,
+        moving_cooldown: f32,
+        #[serde(default)]
+        pattern: ProjectilePattern,
+    },
+    /// Walks towards the player, winds up once in range and then charges in a straight line,
+    /// killing every enemy in its way. It can only be hooked while recovering from a charge.
+    Charger {
+        charge_range: f32,
+        charge_speed: f32,
+        wind_up: f32,
+        charge_duration: f32,
+        recovery: f32,
+    },
+    /// Slowly follows the player and alternates between shooting radial volleys of arrows
+    /// and summoning swarms of `swarm_enemy`. It only dies after its weak point was opened
+    /// with the hook, which stays open for `weak_point_duration` seconds.
+    Boss {
+        phase_duration: f32,
+        volley_interval: f32,
+        volley_arrows: u32,
+        swarm_interval: f32,
+        swarm_size: u32,
+        swarm_enemy: String,
+        weak_point_duration: f32,
+    },
+}
+
+/// What happens when the enemy dies, in addition to being despawned.
+#[derive(Deserialize, Component, Clone)]
+pub enum DeathBehaviour {
+    /// Split into `min_count` to `max_count` enemies of the type `into`.
+    Split {
+        into: String,
+        min_count: u32,
+        max_count: u32,
+    },
+}
+
+#[derive(Deserialize, Clone)]
+pub struct ShieldDefinition {
+    /// The hit sources that the shield deflects when they come from the front.
+    pub blocks: Vec<HitSource>,
+    /// How far the shield covers to either side of its facing, in degrees.
+    pub half_angle: f32,
+    /// How fast the shield turns towards the player, in radians per second.
+    pub turn_speed: f32,
+    /// The speed at which the enemy is pushed back when the shield deflects a hit.
+    pub knockback: f32,
+}
+
+/// Keeps enemies from stacking on top of each other while they move towards the player.
+/// The weights are relative to the pull towards the player, which is `1.0`.
+#[derive(Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct SteeringDefinition {
+    /// Enemies closer than this push each other apart.
+    pub separation_radius: f32,
+    pub separation: f32,
+    /// Enemies are pulled towards the center of the enemies within this radius.
+    pub cohesion_radius: f32,
+    pub cohesion: f32,
+    /// Enemies steer around the closest enemy in their way that is at most this far ahead.
+    pub avoidance_distance: f32,
+    pub avoidance: f32,
+}
+
+impl Default for SteeringDefinition {
+    fn default() -> Self {
+        Self {
+            separation_radius: 36.0,
+            separation: 1.2,
+            cohesion_radius: 0.0,
+            cohesion: 0.0,
+            avoidance_distance: 50.0,
+            avoidance: 0.8,
+        }
+    }
+}
+
+impl ColliderShape {
+    pub fn collider(&self) -> Collider {
+        match *self {
+            ColliderShape::Ball { radius } => Collider::ball(radius),
+            ColliderShape::Capsule { a, b, radius } => Collider::capsule(a, b, radius),
+            ColliderShape::Cuboid { half_x, half_y } => Collider::cuboid(half_x, half_y),
+        }
+    }
+}
+
+impl DirectorConfig {
+    pub fn difficulty_at(&self, time: f32) -> DifficultyPoint {
+        let next = self.difficulty.partition_point(|point| point.time <= time);
+        if next == 0 {
+            return self.difficulty[0];
+        }
+        if next == self.difficulty.len() {
+            return self.difficulty[next - 1];
+        }
+
+        let (a, b) = (self.difficulty[next - 1], self.difficulty[next]);
+        let t = (time - a.time) / (b.time - a.time);
+        DifficultyPoint {
+            time,
+            spawn_rate: a.spawn_rate + (b.spawn_rate - a.spawn_rate) * t,
+            max_alive: (a.max_alive as f32 + (b.max_alive as f32 - a.max_alive as f32) * t).round()
+                as u32,
+        }
+    }
+}
+
+impl EnemyDefinition {
+    pub fn weight_at(&self, wave: u32) -> f32 {
+        if wave < self.first_wave {
+            return 0.0;
+        }
+        self.spawn_weight + self.weight_per_wave * (wave - self.first_wave) as f32
+    }
+}
+
+impl EnemyRoster {
+    pub fn get(&self, name: &str) -> Option<&EnemyDefinition> {
+        self.enemies.iter().find(|enemy| enemy.name == name)
+    }
+
+    /// Pick a random enemy according to the spawn weights in the given wave.
+    pub fn pick<R: Rng + ?Sized>(&self, rng: &mut R, wave: u32) -> Option<&EnemyDefinition> {
+        let weights = match WeightedIndex::new(self.enemies.iter().map(|e| e.weight_at(wave))) {
+            Ok(r) => r,
+            Err(err) => {
+                error!("failed to pick an enemy to spawn, {}", err);
+                return None;
+            }
+        };
+        self.enemies.get(weights.sample(rng))
+    }
+}
+
+#[derive(Deserialize)]
+struct RosterFile {
+    director: DirectorConfig,
+    enemies: Vec<DefinitionFile>,
+}
+
+/// The sprites, shadow, collider and steering can be left out if they are the same as the `base`.
+#[derive(Deserialize)]
+struct DefinitionFile {
+    name: String,
+    /// An enemy listed before this one.
+    #[serde(default)]
+    base: Option<String>,
+    #[serde(default)]
+    texture: Option<String>,
+    #[serde(default)]
+    atlas: Option<AtlasFile>,
+    #[serde(default)]
+    tint: Option<(f32, f32, f32)>,
+    #[serde(default)]
+    scale: Option<f32>,
+    #[serde(default)]
+    animations: Option<AnimationsFile>,
+    #[serde(default)]
+    shadow: Option<ShadowFile>,
+    #[serde(default)]
+    collider: Option<ColliderFile>,
+    score: u32,
+    move_speed: f32,
+    behaviour: EnemyBehaviour,
+    #[serde(default)]
+    on_death: Option<DeathBehaviour>,
+    #[serde(default)]
+    shield: Option<ShieldDefinition>,
+    /// `Some(None)` turns steering off, if it's left out the `base` or the defaults are used.
+    #[serde(default, deserialize_with = "deserialize_listed")]
+    steering: Option<Option<SteeringDefinition>>,
+    spawn_weight: f32,
+    #[serde(default)]
+    weight_per_wave: f32,
+    #[serde(default)]
+    first_wave: u32,
+}
+
+/// Tells a field that is left out (`None`) apart from one that is set to `None` (`Some(None)`).
+fn deserialize_listed<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+impl DefinitionFile {
+    /// Take the sprites, shadow, collider and steering that aren't set from `base`.
+    fn inherit(&mut self, base: &DefinitionFile) {
+        fn inherit<T: Clone>(field: &mut Option<T>, base: &Option<T>) {
+            if field.is_none() {
+                *field = base.clone();
+            }
+        }
+
+        inherit(&mut self.texture, &base.texture);
+        inherit(&mut self.atlas, &base.atlas);
+        inherit(&mut self.scale, &base.scale);
+        inherit(&mut self.animations, &base.animations);
+        inherit(&mut self.shadow, &base.shadow);
+        inherit(&mut self.collider, &base.collider);
+        inherit(&mut self.steering, &base.steering);
+    }
+}
+
+#[derive(Deserialize, Clone)]
+struct AtlasFile {
+    tile_size: Vec2,
+    columns: usize,
+    rows: usize,
+}
+
+#[derive(Deserialize, Clone)]
+struct AnimationsFile {
+    idle: String,
+    #[serde(default)]
+    moving: Option<String>,
+    #[serde(default)]
+    attacking: Option<String>,
+    #[serde(default)]
+    stunned: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+struct ShadowFile {
+    texture: String,
+    offset: Vec2,
+}
+
+#[derive(Deserialize, Clone)]
+struct ColliderFile {
+    shape: ColliderShape,
+    offset: Vec2,
+}
+
+#[derive(Debug)]
+pub enum EnemyRosterLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+    Invalid(&'static str),
+}
+
+impl fmt::Display for EnemyRosterLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnemyRosterLoaderError::Io(err) => write!(f, "failed to read enemy roster, {}", err),
+            EnemyRosterLoaderError::Ron(err) => write!(f, "failed to parse enemy roster, {}", err),
+            EnemyRosterLoaderError::Invalid(reason) => {
+                write!(f, "invalid enemy roster, {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EnemyRosterLoaderError {}
+
+impl From<std::io::Error> for EnemyRosterLoaderError {
+    fn from(err: std::io::Error) -> Self {
+        EnemyRosterLoaderError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for EnemyRosterLoaderError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        EnemyRosterLoaderError::Ron(err)
+    }
+}
+
+fn load_definition(
+    file: DefinitionFile,
+    load_context: &mut LoadContext,
+) -> Result<EnemyDefinition, EnemyRosterLoaderError> {
+    let (texture, atlas, animations, shadow, collider) = match (
+        file.texture,
+        file.atlas,
+        file.animations,
+        file.shadow,
+        file.collider,
+    ) {
+        (Some(texture), Some(atlas), Some(animations), Some(shadow), Some(collider)) => {
+            (texture, atlas, animations, shadow, collider)
+        }
+        _ => {
+            return Err(EnemyRosterLoaderError::Invalid(
+                "an enemy lacks sprites, a shadow or a collider and has no base with them",
+            ))
+        }
+    };
+
+    let texture = load_context.load(texture);
+    let atlas = load_context.add_labeled_asset(
+        format!("{}_atlas", file.name),
+        TextureAtlas::from_grid(
+            texture,
+            atlas.tile_size,
+            atlas.columns,
+            atlas.rows,
+            None,
+            None,
+        ),
+    );
+
+    let idle: Handle<AnimationClip2D> = load_context.load(animations.idle);
+    let mut clip_or_idle = |path: Option<String>| match path {
+        Some(path) => load_context.load(path),
+        None => idle.clone(),
+    };
+    let animations = EnemyAnimations {
+        moving: clip_or_idle(animations.moving),
+        attacking: clip_or_idle(animations.attacking),
+        stunned: clip_or_idle(animations.stunned),
+        idle,
+    };
+
+    Ok(EnemyDefinition {
+        name: file.name,
+        atlas,
+        tint: file
+            .tint
+            .map_or(Color::WHITE, |(r, g, b)| Color::rgb(r, g, b)),
+        scale: file.scale.unwrap_or(DEFAULT_SCALE),
+        animations,
+        shadow: load_context.load(shadow.texture),
+        shadow_offset: shadow.offset,
+        collider: collider.shape,
+        collider_offset: collider.offset,
+        score: file.score,
+        move_speed: file.move_speed,
+        behaviour: file.behaviour,
+        on_death: file.on_death,
+        shield: file.shield,
+        steering: file
+            .steering
+            .unwrap_or_else(|| Some(SteeringDefinition::default())),
+        spawn_weight: file.spawn_weight,
+        weight_per_wave: file.weight_per_wave,
+        first_wave: file.first_wave,
+    })
+}
+
+#[derive(Default)]
+struct EnemyRosterLoader;
+
+impl AssetLoader for EnemyRosterLoader {
+    type Asset = EnemyRoster;
+    type Settings = ();
+    type Error = EnemyRosterLoaderError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<EnemyRoster, EnemyRosterLoaderError>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let file: RosterFile = ron::de::from_bytes(&bytes)?;
+            if file.director.difficulty.is_empty() {
+                return Err(EnemyRosterLoaderError::Invalid(
+                    "the difficulty curve has no points",
+                ));
+            }
+            if file
+                .director
+                .difficulty
+                .windows(2)
+                .any(|points| points[0].time > points[1].time)
+            {
+                return Err(EnemyRosterLoaderError::Invalid(
+                    "the points of the difficulty curve aren't sorted by time",
+                ));
+            }
+            if file.director.wave_duration <= 0.0 {
+                return Err(EnemyRosterLoaderError::Invalid(
+                    "the wave duration must be positive",
+                ));
+            }
+
+            let mut definitions: Vec<DefinitionFile> = Vec::with_capacity(file.enemies.len());
+            for mut definition in file.enemies {
+                if let Some(base) = &definition.base {
+                    let base = match definitions.iter().find(|other| &other.name == base) {
+                        Some(r) => r,
+                        None => {
+                            return Err(EnemyRosterLoaderError::Invalid(
+                                "the base of an enemy isn't listed before it",
+                            ))
+                        }
+                    };
+                    definition.inherit(base);
+                }
+                definitions.push(definition);
+            }
+
+            let roster = EnemyRoster {
+                director: file.director,
+                enemies: definitions
+                    .into_iter()
+                    .map(|definition| load_definition(definition, load_context))
+                    .collect::<Result<_, _>>()?,
+            };
+            for definition in &roster.enemies {
+                if let Some(DeathBehaviour::Split {
+                    into,
+                    min_count,
+                    max_count,
+                }) = &definition.on_death
+                {
+                    if roster.get(into).is_none() {
+                        return Err(EnemyRosterLoaderError::Invalid(
+                            "an enemy splits into an enemy that doesn't exist",
+                        ));
+                    }
+                    if min_count > max_count {
+                        return Err(EnemyRosterLoaderError::Invalid(
+                            "the min count of a split is larger than the max count",
+                        ));
+                    }
+                }
+                if let EnemyBehaviour::Boss { swarm_enemy, .. } = &definition.behaviour {
+                    if roster.get(swarm_enemy).is_none() {
+                        return Err(EnemyRosterLoaderError::Invalid(
+                            "a boss summons an enemy that doesn't exist",
+                        ));
+                    }
+                }
+            }
+            if let Some(boss) = &roster.director.boss {
+                if roster.get(&boss.enemy).is_none() {
+                    return Err(EnemyRosterLoaderError::Invalid(
+                        "the boss schedule names an enemy that doesn't exist",
+                    ));
+                }
+            }
+            Ok(roster)
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["enemies.ron"]
+    }
+}
+
+pub struct EnemyDefinitionPlugin;
+
+impl Plugin for EnemyDefinitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<EnemyRoster>()
+            .init_asset_loader::<EnemyRosterLoader>();
+    }
+}
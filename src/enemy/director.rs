@@ -0,0 +1,152 @@
+use std::collections::VecDeque;
+use std::f32::consts::TAU;
+
+use rand::Rng;
+
+use bevy::prelude::*;
+
+use crate::{
+    player::{kill_counter::KillCounter, speed_timer::SpeedTimer, Player},
+    world::{determinism::AddTickEvent, seed::GameRng},
+    GameAssets, GameState, GameplaySet,
+};
+
+use super::{
+    boss_alive, enemy_spawning_active, spawn::SPAWN_OFFSET, spawn_enemy, Enemy, EnemyRoster,
+};
+
+/// Sent when a new wave starts, the first wave of a run is `1`.
+#[derive(Event)]
+pub struct WaveStarted {
+    pub wave: u32,
+}
+
+/// Decides when and which enemies spawn.
+/// The spawn rate follows the difficulty curve of the `EnemyRoster`
+/// and goes up the faster the player kills enemies.
+#[derive(Resource, Default)]
+pub struct EnemyDirector {
+    wave: u32,
+    /// Enemies that are due to spawn, the fraction carries over to the next tick.
+    pending_spawns: f32,
+    last_kills: u32,
+    /// The elapsed time of every kill inside the kill rate window.
+    kill_times: VecDeque<f32>,
+    kill_rate_window: f32,
+}
+
+impl EnemyDirector {
+    pub fn wave(&self) -> u32 {
+        self.wave
+    }
+
+    /// Kills per second over the kill rate window.
+    pub fn kill_rate(&self) -> f32 {
+        if self.kill_rate_window <= 0.0 {
+            return 0.0;
+        }
+        self.kill_times.len() as f32 / self.kill_rate_window
+    }
+}
+
+fn track_kills(
+    assets: Res<GameAssets>,
+    rosters: Res<Assets<EnemyRoster>>,
+    speed_timer: Res<SpeedTimer>,
+    kill_counter: Res<KillCounter>,
+    mut director: ResMut<EnemyDirector>,
+) {
+    let roster = match rosters.get(&assets.enemy_roster) {
+        Some(r) => r,
+        None => return,
+    };
+
+    let now = speed_timer.elapsed;
+    let new_kills = kill_counter.kills().saturating_sub(director.last_kills);
+    director.last_kills = kill_counter.kills();
+    director.kill_rate_window = roster.director.kill_rate_window;
+    for _ in 0..new_kills {
+        director.kill_times.push_back(now);
+    }
+
+    let window = director.kill_rate_window;
+    while director
+        .kill_times
+        .front()
+        .is_some_and(|time| now - time > window)
+    {
+        director.kill_times.pop_front();
+    }
+}
+
+fn direct_enemies(
+    mut commands: Commands,
+    time: Res<Time>,
+    assets: Res<GameAssets>,
+    rosters: Res<Assets<EnemyRoster>>,
+    speed_timer: Res<SpeedTimer>,
+    mut game_rng: ResMut<GameRng>,
+    mut director: ResMut<EnemyDirector>,
+    q_player: Query<&Transform, With<Player>>,
+    q_enemies: Query<&Enemy>,
+    mut ev_wave_started: EventWriter<WaveStarted>,
+) {
+    let player_transform = match q_player.get_single() {
+        Ok(r) => r,
+        Err(_) => return,
+    };
+    let roster = match rosters.get(&assets.enemy_roster) {
+        Some(r) => r,
+        None => return,
+    };
+    let config = &roster.director;
+
+    let wave = (speed_timer.elapsed / config.wave_duration) as u32 + 1;
+    if wave != director.wave {
+        director.wave = wave;
+        director.pending_spawns += config.wave_burst as f32;
+        ev_wave_started.send(WaveStarted { wave });
+    }
+
+    let difficulty = config.difficulty_at(speed_timer.elapsed);
+    let spawn_rate = difficulty.spawn_rate + director.kill_rate() * config.kill_rate_factor;
+    director.pending_spawns += spawn_rate * time.delta_seconds();
+
+    let alive = q_enemies.iter().filter(|enemy| !enemy.disabled).count() as u32;
+    let free = difficulty.max_alive.saturating_sub(alive);
+    let count = (director.pending_spawns as u32).min(free);
+    director.pending_spawns -= count as f32;
+    // Don't build up a backlog while the cap is reached.
+    director.pending_spawns = director.pending_spawns.min(config.wave_burst.max(1) as f32);
+
+    for _ in 0..count {
+        let definition = match roster.pick(&mut **game_rng, wave) {
+            Some(r) => r,
+            None => return,
+        };
+        let pos = player_transform.translation
+            + Quat::from_rotation_z(game_rng.gen_range(0.0..TAU)).mul_vec3(Vec3::X) * SPAWN_OFFSET;
+        spawn_enemy(&mut commands, definition, pos);
+    }
+}
+
+fn reset_director(mut director: ResMut<EnemyDirector>) {
+    *director = EnemyDirector::default();
+}
+
+pub struct EnemyDirectorPlugin;
+
+impl Plugin for EnemyDirectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemyDirector>()
+            .add_tick_event::<WaveStarted>()
+            .add_systems(
+                FixedUpdate,
+                (track_kills, direct_enemies.run_if(not(boss_alive)))
+                    .chain()
+                    .in_set(GameplaySet)
+                    .run_if(enemy_spawning_active),
+            )
+            .add_systems(OnEnter(GameState::Restart), reset_director);
+    }
+}
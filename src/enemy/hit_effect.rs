@@ -1,9 +1,11 @@
 use bevy::prelude::*;
 use bevy_trickfilm::prelude::*;
 
-use crate::{world::camera::YSort, GameAssets, GameState};
+use crate::{world::camera::YSort, GameAssets, GameplaySet};
 
-use super::spawn::DespawnEnemy;
+use super::{spawn::DespawnEnemy, HitBlocked};
+
+const CLANG_COLOR: Color = Color::rgb(0.65, 0.85, 1.0);
 
 #[derive(Component)]
 struct HitEffect;
@@ -30,6 +32,32 @@ fn spawn_hit_effects(
     }
 }
 
+fn spawn_clang_effects(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut ev_hit_blocked: EventReader<HitBlocked>,
+) {
+    for ev in ev_hit_blocked.read() {
+        let mut animator = AnimationPlayer2D::default();
+        animator.play(assets.enemy_hit_animations[0].clone());
+
+        commands.spawn((
+            HitEffect,
+            animator,
+            YSort(1.0),
+            SpriteSheetBundle {
+                texture_atlas: assets.enemy_hit.clone(),
+                sprite: TextureAtlasSprite {
+                    color: CLANG_COLOR,
+                    ..default()
+                },
+                transform: Transform::from_translation(ev.pos.extend(0.0)),
+                ..default()
+            },
+        ));
+    }
+}
+
 fn despawn_hit_effects(
     mut commands: Commands,
     q_hit_effects: Query<(Entity, &AnimationPlayer2D), With<HitEffect>>,
@@ -47,7 +75,7 @@ impl Plugin for EnemyHitEffectPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (spawn_hit_effects, despawn_hit_effects).run_if(in_state(GameState::Gaming)),
+            (spawn_hit_effects, spawn_clang_effects, despawn_hit_effects).in_set(GameplaySet),
         );
     }
 }
@@ -1,33 +1,109 @@
 mod archer;
 mod bat;
+mod boss;
+mod charger;
 mod collision;
+mod culling;
+mod definition;
+mod director;
 mod hit_effect;
+mod shield;
+mod spatial_grid;
 mod spawn;
+mod splitter;
+mod steering;
+mod vulnerability;
+
+pub use archer::{spawn_archer_projectile, ArcherProjectile, ProjectilePattern};
+pub use boss::{boss_alive, Boss};
+pub use definition::{
+    BossSchedule, ColliderShape, DeathBehaviour, DifficultyPoint, DirectorConfig, EnemyAnimations,
+    EnemyBehaviour, EnemyDefinition, EnemyRoster, ShieldDefinition, SteeringDefinition,
+};
+pub use director::{EnemyDirector, WaveStarted};
+pub use shield::Shield;
+pub use spatial_grid::{GridEntry, SpatialGrid, SpatialGridSet};
+pub use spawn::{spawn_enemy, DespawnEnemy};
+pub use steering::{steer, EnemySteeringSet, Steering};
+pub use vulnerability::{EnemyVulnerability, HitBlocked};
 
 use bevy::prelude::*;
+use serde::Deserialize;
 
-const REFLECTION_PROJECTILE_SCORE_ADDITION: u32 = 100;
-const DASH_SCORE_MULTIPLIYER: f32 = 0.35;
+pub const REFLECTION_PROJECTILE_SCORE_ADDITION: u32 = 100;
+pub const DASH_SCORE_MULTIPLIYER: f32 = 0.35;
+/// Awarded for every generation of a family once all of its members are dead.
+pub const FAMILY_CLEAR_SCORE_ADDITION: u32 = 250;
 
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
+            definition::EnemyDefinitionPlugin,
+            director::EnemyDirectorPlugin,
             hit_effect::EnemyHitEffectPlugin,
             spawn::EnemySpawnPlugin,
             bat::EnemyBatPlugin,
             archer::EnemyArcherPlugin,
+            charger::EnemyChargerPlugin,
+            boss::EnemyBossPlugin,
             collision::EnemyCollisionPlugin,
-        ));
+            culling::EnemyCullingPlugin,
+            splitter::EnemySplitterPlugin,
+            shield::EnemyShieldPlugin,
+            vulnerability::EnemyVulnerabilityPlugin,
+            steering::EnemySteeringPlugin,
+            spatial_grid::EnemySpatialGridPlugin,
+        ))
+        .init_resource::<EnemySpawning>();
+    }
+}
+
+/// Whether enemies spawn on their own, tests turn this off to place them by hand.
+#[derive(Resource)]
+pub struct EnemySpawning {
+    pub active: bool,
+}
+
+impl Default for EnemySpawning {
+    fn default() -> Self {
+        Self { active: true }
     }
 }
 
+pub fn enemy_spawning_active(enemy_spawning: Res<EnemySpawning>) -> bool {
+    enemy_spawning.active
+}
+
 #[derive(Component, Default, Clone)]
 pub struct Enemy {
+    /// The name of the enemy in the roster.
+    pub name: String,
     pub stunned: bool,
     pub disabled: bool,
+    /// The hook passes through the enemy instead of pulling the player to it.
+    pub hook_immune: bool,
+    /// Enemies that split off another enemy share its family.
+    pub family: Option<u32>,
+    /// How many splits away from the first enemy of the family this one is.
+    pub generation: u32,
     pub score: u32,
+    /// What killed the enemy, if anything.
+    pub killed_by: Option<HitSource>,
+    /// Which strike of the strike chain killed the enemy.
+    pub strike_index: Option<usize>,
+}
+
+/// What hit an enemy, enemies can be immune to some of them.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HitSource {
+    Strike,
+    Dash,
+    DashLanding,
+    Reflection,
+    Hook,
+    Charge,
 }
 
 #[derive(Component, Default)]
@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+
+use crate::{player::Player, utils::quat_from_vec2, GameAssets, GameplaySet};
+
+use super::{HitBlocked, HitSource, ShieldDefinition};
+
+const SHIELD_SPRITE_SIZE: Vec2 = Vec2::new(3.0, 16.0);
+const SHIELD_SPRITE_DISTANCE: f32 = 12.0;
+const SHIELD_COLOR: Color = Color::rgb(0.65, 0.85, 1.0);
+/// How fast the knockback velocity decays, higher means a shorter knockback.
+const KNOCKBACK_DAMPING: f32 = 10.0;
+
+/// A shield in front of the enemy that deflects some hit sources.
+/// It turns towards the player, so flanking the enemy gets around it.
+#[derive(Component)]
+pub struct Shield {
+    blocks: Vec<HitSource>,
+    /// The cosine of the half angle that the shield covers.
+    min_cos: f32,
+    turn_speed: f32,
+    knockback: f32,
+    facing: Vec2,
+    knockback_velocity: Vec2,
+}
+
+#[derive(Component)]
+struct ShieldSprite;
+
+impl Shield {
+    pub fn new(definition: &ShieldDefinition) -> Self {
+        Self {
+            blocks: definition.blocks.clone(),
+            min_cos: definition.half_angle.to_radians().cos(),
+            turn_speed: definition.turn_speed,
+            knockback: definition.knockback,
+            facing: Vec2::ZERO,
+            knockback_velocity: Vec2::ZERO,
+        }
+    }
+
+    /// Whether a hit of the given source coming from `hit_pos` is deflected.
+    pub fn blocks(&self, source: HitSource, enemy_pos: Vec2, hit_pos: Vec2) -> bool {
+        if !self.blocks.contains(&source) {
+            return false;
+        }
+        let dir = (hit_pos - enemy_pos).normalize_or_zero();
+        self.facing.dot(dir) >= self.min_cos
+    }
+
+    /// Where on the shield hits land.
+    pub fn impact_pos(&self, enemy_pos: Vec2) -> Vec2 {
+        enemy_pos + self.facing * SHIELD_SPRITE_DISTANCE * 2.0
+    }
+}
+
+fn spawn_shield_sprites(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    q_shields: Query<Entity, Added<Shield>>,
+) {
+    for entity in &q_shields {
+        let sprite = commands
+            .spawn((
+                ShieldSprite,
+                SpriteBundle {
+                    texture: assets.white_pixel.clone(),
+                    sprite: Sprite {
+                        color: SHIELD_COLOR,
+                        custom_size: Some(SHIELD_SPRITE_SIZE),
+                        ..default()
+                    },
+                    transform: Transform::from_translation(Vec3::new(0.0, 0.0, 0.1)),
+                    ..default()
+                },
+            ))
+            .id();
+        commands.entity(entity).push_children(&[sprite]);
+    }
+}
+
+fn turn_shields(
+    time: Res<Time>,
+    q_player: Query<&Transform, With<Player>>,
+    mut q_shields: Query<(&Transform, &mut Shield), Without<Player>>,
+) {
+    let player_pos = match q_player.get_single() {
+        Ok(r) => r.translation.truncate(),
+        Err(_) => return,
+    };
+
+    for (transform, mut shield) in &mut q_shields {
+        let target = (player_pos - transform.translation.truncate()).normalize_or_zero();
+        if shield.facing == Vec2::ZERO {
+            shield.facing = target;
+            continue;
+        }
+
+        let angle = shield.facing.angle_between(target);
+        let max_step = shield.turn_speed * time.delta_seconds();
+        shield.facing = Vec2::from_angle(angle.clamp(-max_step, max_step)).rotate(shield.facing);
+    }
+}
+
+fn update_shield_sprites(
+    q_shields: Query<&Shield>,
+    mut q_sprites: Query<(&Parent, &mut Transform), With<ShieldSprite>>,
+) {
+    for (parent, mut transform) in &mut q_sprites {
+        let shield = match q_shields.get(parent.get()) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+
+        transform.translation = (shield.facing * SHIELD_SPRITE_DISTANCE).extend(0.1);
+        transform.rotation = quat_from_vec2(shield.facing);
+    }
+}
+
+fn knock_back(
+    mut q_shields: Query<(&Transform, &mut Shield)>,
+    mut ev_hit_blocked: EventReader<HitBlocked>,
+) {
+    for ev in ev_hit_blocked.read() {
+        let (transform, mut shield) = match q_shields.get_mut(ev.enemy) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        let dir = (transform.translation.truncate() - ev.pos).normalize_or_zero();
+        shield.knockback_velocity = dir * shield.knockback;
+    }
+}
+
+fn apply_knockback(time: Res<Time>, mut q_shields: Query<(&mut Transform, &mut Shield)>) {
+    for (mut transform, mut shield) in &mut q_shields {
+        if shield.knockback_velocity == Vec2::ZERO {
+            continue;
+        }
+
+        transform.translation += (shield.knockback_velocity * time.delta_seconds()).extend(0.0);
+        shield.knockback_velocity *= (-KNOCKBACK_DAMPING * time.delta_seconds()).exp();
+        if shield.knockback_velocity.length_squared() < 1.0 {
+            shield.knockback_velocity = Vec2::ZERO;
+        }
+    }
+}
+
+pub struct EnemyShieldPlugin;
+
+impl Plugin for EnemyShieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                spawn_shield_sprites,
+                turn_shields,
+                update_shield_sprites.after(turn_shields),
+                knock_back,
+                apply_knockback.after(knock_back),
+            )
+                .in_set(GameplaySet),
+        );
+    }
+}
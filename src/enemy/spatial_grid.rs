@@ -0,0 +1,181 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::GameplaySet;
+
+use super::Enemy;
+
+/// The size of the cells of the grid, should be about the radius of the most common queries.
+const CELL_SIZE: f32 = 64.0;
+
+/// The systems that rebuild the `SpatialGrid`, use it after this set.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpatialGridSet;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridEntry {
+    pub entity: Entity,
+    pub pos: Vec2,
+}
+
+/// A uniform grid over the positions of all enemies that aren't disabled, rebuilt every tick.
+/// Use it instead of iterating all enemies whenever only the ones in some area matter.
+#[derive(Resource, Default)]
+pub struct SpatialGrid {
+    cells: HashMap<IVec2, Vec<GridEntry>>,
+}
+
+impl SpatialGrid {
+    fn cell(pos: Vec2) -> IVec2 {
+        (pos / CELL_SIZE).floor().as_ivec2()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    pub fn insert(&mut self, entity: Entity, pos: Vec2) {
+        self.cells
+            .entry(Self::cell(pos))
+            .or_default()
+            .push(GridEntry { entity, pos });
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// All enemies in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &GridEntry> {
+        self.cells.values().flatten()
+    }
+
+    /// All enemies at most `radius` away from `pos`.
+    pub fn in_radius(&self, pos: Vec2, radius: f32) -> impl Iterator<Item = &GridEntry> {
+        let min = Self::cell(pos - Vec2::splat(radius));
+        let max = Self::cell(pos + Vec2::splat(radius));
+        (min.x..=max.x)
+            .flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(move |entry| entry.pos.distance_squared(pos) <= radius * radius)
+    }
+
+    /// All enemies at most `radius` away from `pos` that are within `half_angle` radians
+    /// to either side of `dir`.
+    pub fn in_cone(
+        &self,
+        pos: Vec2,
+        dir: Vec2,
+        half_angle: f32,
+        radius: f32,
+    ) -> impl Iterator<Item = &GridEntry> {
+        let dir = dir.normalize_or_zero();
+        let min_cos = half_angle.cos();
+        self.in_radius(pos, radius)
+            .filter(move |entry| (entry.pos - pos).normalize_or_zero().dot(dir) >= min_cos)
+    }
+
+    /// The closest enemy at most `max_radius` away from `pos` that passes the `filter`.
+    pub fn nearest(
+        &self,
+        pos: Vec2,
+        max_radius: f32,
+        filter: impl Fn(&GridEntry) -> bool,
+    ) -> Option<&GridEntry> {
+        let center = Self::cell(pos);
+        let rings = (max_radius / CELL_SIZE).ceil() as i32 + 1;
+        let mut best: Option<(f32, &GridEntry)> = None;
+
+        for ring in 0..=rings {
+            // Everything in this ring and beyond is at least this far away.
+            let min_distance = (ring - 1).max(0) as f32 * CELL_SIZE;
+            if best.is_some_and(|(distance, _)| distance <= min_distance * min_distance) {
+                break;
+            }
+
+            for x in -ring..=ring {
+                for y in -ring..=ring {
+                    if x.abs() != ring && y.abs() != ring {
+                        continue;
+                    }
+                    let entries = match self.cells.get(&(center + IVec2::new(x, y))) {
+                        Some(r) => r,
+                        None => continue,
+                    };
+                    for entry in entries {
+                        let distance = entry.pos.distance_squared(pos);
+                        if distance > max_radius * max_radius || !filter(entry) {
+                            continue;
+                        }
+                        if best.map_or(true, |(best_distance, _)| distance < best_distance) {
+                            best = Some((distance, entry));
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|(_, entry)| entry)
+    }
+
+    /// All enemies further than `radius` away from `pos`.
+    /// Cells that are completely inside or outside of the radius are skipped or taken as a whole.
+    pub fn outside_radius(&self, pos: Vec2, radius: f32) -> impl Iterator<Item = &GridEntry> {
+        let radius_squared = radius * radius;
+        self.cells
+            .iter()
+            .filter_map(move |(cell, entries)| {
+                let min = cell.as_vec2() * CELL_SIZE;
+                let max = min + Vec2::splat(CELL_SIZE);
+                let closest = pos.clamp(min, max);
+                let farthest = Vec2::new(
+                    if pos.x - min.x > max.x - pos.x {
+                        min.x
+                    } else {
+                        max.x
+                    },
+                    if pos.y - min.y > max.y - pos.y {
+                        min.y
+                    } else {
+                        max.y
+                    },
+                );
+
+                if farthest.distance_squared(pos) <= radius_squared {
+                    None
+                } else if closest.distance_squared(pos) > radius_squared {
+                    Some((entries, false))
+                } else {
+                    Some((entries, true))
+                }
+            })
+            .flat_map(move |(entries, partial)| {
+                entries.iter().filter(move |entry| {
+                    !partial || entry.pos.distance_squared(pos) > radius_squared
+                })
+            })
+    }
+}
+
+fn rebuild_grid(mut grid: ResMut<SpatialGrid>, q_enemies: Query<(Entity, &Transform, &Enemy)>) {
+    grid.clear();
+    for (entity, transform, enemy) in &q_enemies {
+        if !enemy.disabled {
+            grid.insert(entity, transform.translation.truncate());
+        }
+    }
+}
+
+pub struct EnemySpatialGridPlugin;
+
+impl Plugin for EnemySpatialGridPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialGrid>().add_systems(
+            FixedUpdate,
+            (rebuild_grid,).in_set(SpatialGridSet).in_set(GameplaySet),
+        );
+    }
+}
@@ -1,36 +1,159 @@
 use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_trickfilm::prelude::*;
 
 use crate::{
-    player::{kill_counter::KillCounter, score::PlayerScore, Player},
+    player::{combo::Combo, kill_counter::KillCounter, score::PlayerScore, Player},
     ui::world_text::SpawnWorldText,
-    GameState,
+    world::{camera::YSort, determinism::AddTickEvent},
+    GameState, GameplaySet,
 };
 
-use super::{Enemy, EnemyProjectile};
+use super::{
+    archer::EnemyArcher, bat::EnemyBat, charger::EnemyCharger, shield::Shield, steering::Steering,
+    Boss, DeathBehaviour, Enemy, EnemyBehaviour, EnemyDefinition, EnemyProjectile, SpatialGrid,
+    SpatialGridSet,
+};
 
 pub const SPAWN_OFFSET: f32 = 900.0;
 const MAX_PLAYER_DISTANCE: f32 = 1200.0;
 
 #[derive(Event)]
 pub struct DespawnEnemy {
-    enemy: Enemy,
+    pub enemy: Enemy,
     pub pos: Vec2,
+    pub on_death: Option<DeathBehaviour>,
+    /// The combo count including this kill.
+    pub combo: u32,
+}
+
+pub fn spawn_enemy(commands: &mut Commands, definition: &EnemyDefinition, pos: Vec3) -> Entity {
+    let mut animator = AnimationPlayer2D::default();
+    animator.play(definition.animations.idle.clone()).repeat();
+
+    let collider = commands
+        .spawn((
+            definition.collider.collider(),
+            ActiveEvents::COLLISION_EVENTS,
+            CollisionGroups::default(),
+            TransformBundle::from_transform(Transform::from_translation(
+                definition.collider_offset.extend(0.0),
+            )),
+        ))
+        .id();
+
+    let shadow = commands
+        .spawn((
+            YSort(-1.0),
+            SpriteBundle {
+                texture: definition.shadow.clone(),
+                transform: Transform::from_translation(definition.shadow_offset.extend(0.0)),
+                ..default()
+            },
+        ))
+        .id();
+
+    let mut enemy = commands.spawn((
+        Enemy {
+            name: definition.name.clone(),
+            score: definition.score,
+            ..default()
+        },
+        definition.animations.clone(),
+        YSort(0.0),
+        animator,
+        SpriteSheetBundle {
+            sprite: TextureAtlasSprite {
+                color: definition.tint,
+                ..default()
+            },
+            texture_atlas: definition.atlas.clone(),
+            transform: Transform::from_translation(pos).with_scale(Vec3::splat(definition.scale)),
+            ..default()
+        },
+    ));
+    if let Some(on_death) = &definition.on_death {
+        enemy.insert(on_death.clone());
+    }
+    if let Some(shield) = &definition.shield {
+        enemy.insert(Shield::new(shield));
+    }
+    if let Some(steering) = &definition.steering {
+        enemy.insert(Steering::new(steering));
+    }
+
+    match &definition.behaviour {
+        EnemyBehaviour::Bat => enemy.insert(EnemyBat {
+            move_speed: definition.move_speed,
+        }),
+        EnemyBehaviour::Archer {
+            shoot_range,
+            moving_cooldown,
+            pattern,
+        } => enemy.insert(EnemyArcher::new(
+            definition.move_speed,
+            *shoot_range,
+            *moving_cooldown,
+            *pattern,
+        )),
+        EnemyBehaviour::Charger {
+            charge_range,
+            charge_speed,
+            wind_up,
+            charge_duration,
+            recovery,
+        } => enemy.insert(EnemyCharger::new(
+            definition.move_speed,
+            definition.tint,
+            *charge_range,
+            *charge_speed,
+            *wind_up,
+            *charge_duration,
+            *recovery,
+        )),
+        EnemyBehaviour::Boss {
+            phase_duration,
+            volley_interval,
+            volley_arrows,
+            swarm_interval,
+            swarm_size,
+            swarm_enemy,
+            weak_point_duration,
+        } => enemy.insert(Boss::new(
+            definition.move_speed,
+            *phase_duration,
+            *volley_interval,
+            *volley_arrows,
+            *swarm_interval,
+            *swarm_size,
+            swarm_enemy.clone(),
+            *weak_point_duration,
+        )),
+    };
+
+    enemy.push_children(&[shadow, collider]).id()
 }
 
 fn despawn_enemies(
     mut commands: Commands,
     mut death_counter: ResMut<KillCounter>,
     mut player_score: ResMut<PlayerScore>,
-    q_enemies: Query<(Entity, &Transform, &Enemy)>,
+    mut combo: ResMut<Combo>,
+    q_enemies: Query<(Entity, &Transform, &Enemy, Option<&DeathBehaviour>)>,
     mut ev_despawn_enemy: EventWriter<DespawnEnemy>,
 ) {
-    for (entity, transform, enemy) in &q_enemies {
+    for (entity, transform, enemy, on_death) in &q_enemies {
         if enemy.disabled {
+            let mut enemy = enemy.clone();
+            enemy.score = combo.register_kill(enemy.killed_by, enemy.score);
+
             death_counter.increase();
             player_score.add(enemy.score);
             ev_despawn_enemy.send(DespawnEnemy {
-                enemy: enemy.clone(),
+                enemy,
                 pos: transform.translation.truncate(),
+                on_death: on_death.cloned(),
+                combo: combo.count(),
             });
             commands.entity(entity).despawn_recursive();
         }
@@ -60,21 +183,8 @@ fn despawn_all_projectiles(
     }
 }
 
-fn adjust_sprite_flip(
-    q_player: Query<&Transform, With<Player>>,
-    mut q_enemies: Query<(&Transform, &mut TextureAtlasSprite), With<Enemy>>,
-) {
-    let player_pos = match q_player.get_single() {
-        Ok(r) => r.translation,
-        Err(_) => return,
-    };
-
-    for (transform, mut sprite) in &mut q_enemies {
-        sprite.flip_x = player_pos.x < transform.translation.x;
-    }
-}
-
 fn redeploy_enemies(
+    grid: Res<SpatialGrid>,
     q_player: Query<&Transform, With<Player>>,
     mut q_enemies: Query<&mut Transform, (With<Enemy>, Without<Player>)>,
 ) {
@@ -83,19 +193,15 @@ fn redeploy_enemies(
         Err(_) => return,
     };
 
-    for mut enemy_transform in &mut q_enemies {
-        if enemy_transform
-            .translation
-            .truncate()
-            .distance_squared(player_transform.translation.truncate())
-            >= MAX_PLAYER_DISTANCE.powi(2)
-        {
-            let dir = (player_transform.translation - enemy_transform.translation)
-                .truncate()
-                .normalize_or_zero();
-            enemy_transform.translation =
-                player_transform.translation + dir.extend(0.0) * SPAWN_OFFSET;
-        }
+    let player_pos = player_transform.translation.truncate();
+    for entry in grid.outside_radius(player_pos, MAX_PLAYER_DISTANCE) {
+        let mut enemy_transform = match q_enemies.get_mut(entry.entity) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+
+        let dir = (player_pos - entry.pos).normalize_or_zero();
+        enemy_transform.translation = player_transform.translation + dir.extend(0.0) * SPAWN_OFFSET;
     }
 }
 
@@ -104,9 +210,14 @@ fn spawn_score_text(
     mut ev_spawn_world_text: EventWriter<SpawnWorldText>,
 ) {
     for ev in ev_despawn_enemy.read() {
+        let content = if ev.combo > 1 {
+            format!("+{}\n{} COMBO", ev.enemy.score, ev.combo)
+        } else {
+            format!("+{}", ev.enemy.score)
+        };
         ev_spawn_world_text.send(SpawnWorldText {
             pos: ev.pos.extend(0.0),
-            content: format!("+{}", ev.enemy.score),
+            content,
             ..default()
         });
     }
@@ -116,13 +227,15 @@ pub struct EnemySpawnPlugin;
 
 impl Plugin for EnemySpawnPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<DespawnEnemy>()
+        app.add_tick_event::<DespawnEnemy>()
+            .add_systems(
+                FixedUpdate,
+                (redeploy_enemies.after(SpatialGridSet), spawn_score_text).in_set(GameplaySet),
+            )
             .add_systems(
-                Update,
-                (adjust_sprite_flip, redeploy_enemies, spawn_score_text)
-                    .run_if(in_state(GameState::Gaming)),
+                FixedUpdate,
+                (despawn_enemies, despawn_projectiles).after(GameplaySet),
             )
-            .add_systems(Update, (despawn_enemies, despawn_projectiles))
             .add_systems(
                 OnEnter(GameState::Restart),
                 (despawn_all_enemies, despawn_all_projectiles),
@@ -0,0 +1,131 @@
+use std::f32::consts::TAU;
+
+use rand::Rng;
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    player::score::PlayerScore,
+    ui::world_text::{SpawnWorldText, WorldText},
+    world::seed::GameRng,
+    GameAssets, GameState, GameplaySet,
+};
+
+use super::{
+    spawn::DespawnEnemy, spawn_enemy, DeathBehaviour, Enemy, EnemyRoster,
+    FAMILY_CLEAR_SCORE_ADDITION,
+};
+
+/// How far from the dead enemy the split off enemies spawn.
+const SPLIT_SCATTER: f32 = 50.0;
+
+struct Family {
+    alive: u32,
+    generations: u32,
+}
+
+/// The families of enemies that split, keyed by the `Enemy::family` id.
+#[derive(Resource, Default)]
+struct EnemyFamilies {
+    next_id: u32,
+    families: HashMap<u32, Family>,
+}
+
+fn split_enemies(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    rosters: Res<Assets<EnemyRoster>>,
+    mut game_rng: ResMut<GameRng>,
+    mut families: ResMut<EnemyFamilies>,
+    mut player_score: ResMut<PlayerScore>,
+    mut ev_despawn_enemy: EventReader<DespawnEnemy>,
+    mut ev_spawn_world_text: EventWriter<SpawnWorldText>,
+) {
+    let roster = match rosters.get(&assets.enemy_roster) {
+        Some(r) => r,
+        None => return,
+    };
+
+    for ev in ev_despawn_enemy.read() {
+        let split = match &ev.on_death {
+            Some(DeathBehaviour::Split {
+                into,
+                min_count,
+                max_count,
+            }) => roster
+                .get(into)
+                .map(|definition| (definition, game_rng.gen_range(*min_count..=*max_count))),
+            None => None,
+        };
+
+        let family = match (ev.enemy.family, split) {
+            (Some(family), _) => family,
+            (None, Some(_)) => {
+                families.next_id += 1;
+                families.next_id
+            }
+            // Enemies that don't split and don't belong to a family don't need tracking.
+            (None, None) => continue,
+        };
+
+        let children = split.map_or(0, |(_, count)| count);
+        let entry = families.families.entry(family).or_insert(Family {
+            alive: 1,
+            generations: 1,
+        });
+        entry.alive = entry.alive + children - 1;
+        if children > 0 {
+            entry.generations = entry.generations.max(ev.enemy.generation + 2);
+        }
+
+        if entry.alive == 0 {
+            let bonus = FAMILY_CLEAR_SCORE_ADDITION * (entry.generations - 1);
+            families.families.remove(&family);
+            player_score.add(bonus);
+            ev_spawn_world_text.send(SpawnWorldText {
+                pos: ev.pos.extend(0.0),
+                content: format!("FAMILY +{}", bonus),
+                world_text: WorldText {
+                    offset: Vec3::new(0.0, 40.0, 10.0),
+                    ..default()
+                },
+            });
+        }
+
+        let (definition, count) = match split {
+            Some(r) => r,
+            None => continue,
+        };
+        let angle = game_rng.gen_range(0.0..TAU);
+        for i in 0..count {
+            let dir =
+                Quat::from_rotation_z(angle + i as f32 * TAU / count as f32).mul_vec3(Vec3::X);
+            let child = spawn_enemy(
+                &mut commands,
+                definition,
+                ev.pos.extend(0.0) + dir * SPLIT_SCATTER,
+            );
+            commands.entity(child).insert(Enemy {
+                name: definition.name.clone(),
+                score: definition.score,
+                family: Some(family),
+                generation: ev.enemy.generation + 1,
+                ..default()
+            });
+        }
+    }
+}
+
+fn reset_families(mut families: ResMut<EnemyFamilies>) {
+    *families = EnemyFamilies::default();
+}
+
+pub struct EnemySplitterPlugin;
+
+impl Plugin for EnemySplitterPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemyFamilies>()
+            .add_systems(FixedUpdate, (split_enemies,).in_set(GameplaySet))
+            .add_systems(OnEnter(GameState::Restart), reset_families);
+    }
+}
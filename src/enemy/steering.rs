@@ -0,0 +1,140 @@
+use bevy::prelude::*;
+
+use crate::{player::Player, GameplaySet};
+
+use super::{SpatialGrid, SpatialGridSet, SteeringDefinition};
+
+/// Spreads the fallback directions of stacked enemies evenly around the circle.
+const GOLDEN_ANGLE: f32 = 2.399_963;
+
+/// The movement systems that use `Steering` run after this set.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnemySteeringSet;
+
+#[derive(Component)]
+pub struct Steering {
+    definition: SteeringDefinition,
+    /// Added to the direction towards the player, updated every tick.
+    force: Vec2,
+}
+
+impl Steering {
+    pub fn new(definition: &SteeringDefinition) -> Self {
+        Self {
+            definition: *definition,
+            force: Vec2::ZERO,
+        }
+    }
+
+    /// Steer the direction towards the player, the result is normalized.
+    pub fn steer(&self, dir: Vec2) -> Vec2 {
+        (dir + self.force).normalize_or_zero()
+    }
+
+    fn search_radius(&self) -> f32 {
+        self.definition
+            .separation_radius
+            .max(self.definition.cohesion_radius)
+            .max(self.definition.avoidance_distance)
+    }
+}
+
+/// Steer `dir` with the optional steering of an enemy.
+pub fn steer(steering: Option<&Steering>, dir: Vec2) -> Vec2 {
+    match steering {
+        Some(steering) => steering.steer(dir),
+        None => dir,
+    }
+}
+
+/// The direction that `entity` is pushed in when it's on the exact same spot as `other`.
+/// Both derive it from the pair, so they are pushed in opposite directions.
+fn stacked_direction(entity: Entity, other: Entity) -> Vec2 {
+    let dir = Vec2::from_angle(entity.index().min(other.index()) as f32 * GOLDEN_ANGLE);
+    if entity.index() < other.index() {
+        dir
+    } else {
+        -dir
+    }
+}
+
+fn update_steering(
+    grid: Res<SpatialGrid>,
+    q_player: Query<&Transform, With<Player>>,
+    mut q_steering: Query<(Entity, &Transform, &mut Steering), Without<Player>>,
+) {
+    let player_pos = match q_player.get_single() {
+        Ok(r) => r.translation.truncate(),
+        Err(_) => return,
+    };
+
+    for (entity, transform, mut steering) in &mut q_steering {
+        let pos = transform.translation.truncate();
+        let heading = (player_pos - pos).normalize_or_zero();
+        let definition = steering.definition;
+
+        let mut separation = Vec2::ZERO;
+        let mut center = Vec2::ZERO;
+        let mut cohesion_count = 0;
+        // The closest enemy in the way, as the distance along the heading and the offset.
+        let mut obstacle: Option<(f32, Vec2)> = None;
+
+        for other in grid.in_radius(pos, steering.search_radius()) {
+            if other.entity == entity {
+                continue;
+            }
+
+            let offset = other.pos - pos;
+            let distance = offset.length();
+            if distance < definition.separation_radius {
+                let away = if distance > 0.0 {
+                    -offset / distance
+                } else {
+                    stacked_direction(entity, other.entity)
+                };
+                separation += away * (1.0 - distance / definition.separation_radius);
+            }
+            if distance < definition.cohesion_radius {
+                center += other.pos;
+                cohesion_count += 1;
+            }
+
+            let ahead = offset.dot(heading);
+            let side = offset.perp_dot(heading).abs();
+            if ahead > 0.0
+                && ahead < definition.avoidance_distance
+                && side < definition.separation_radius
+                && obstacle.map_or(true, |(closest, _)| ahead < closest)
+            {
+                obstacle = Some((ahead, offset));
+            }
+        }
+
+        let mut force = separation * definition.separation;
+        if cohesion_count > 0 {
+            let to_center = center / cohesion_count as f32 - pos;
+            force += to_center.normalize_or_zero() * definition.cohesion;
+        }
+        if let Some((ahead, offset)) = obstacle {
+            // Steer to the side of the heading that the obstacle isn't on.
+            let side = heading.perp();
+            let away = if offset.dot(side) > 0.0 { -side } else { side };
+            force += away * definition.avoidance * (1.0 - ahead / definition.avoidance_distance);
+        }
+        steering.force = force;
+    }
+}
+
+pub struct EnemySteeringPlugin;
+
+impl Plugin for EnemySteeringPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (update_steering,)
+                .in_set(EnemySteeringSet)
+                .after(SpatialGridSet)
+                .in_set(GameplaySet),
+        );
+    }
+}
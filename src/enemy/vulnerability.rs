@@ -0,0 +1,85 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{audio::PlaySound, world::determinism::AddTickEvent, GameAssets, GameplaySet};
+
+use super::{boss::Boss, shield::Shield, HitSource};
+
+const CLANG_VOLUME: f64 = 0.8;
+const CLANG_PLAYBACK_RATE: f64 = 2.2;
+
+/// Sent when an enemy deflects a hit, for instance with its shield.
+#[derive(Event)]
+pub struct HitBlocked {
+    pub enemy: Entity,
+    pub source: HitSource,
+    /// Where the hit landed.
+    pub pos: Vec2,
+}
+
+/// Decides whether hits on enemies go through.
+/// Every hit on an enemy should go through this check before disabling it.
+#[derive(SystemParam)]
+pub struct EnemyVulnerability<'w, 's> {
+    q_shields: Query<'w, 's, (&'static GlobalTransform, &'static Shield)>,
+    q_bosses: Query<'w, 's, &'static Boss>,
+    ev_hit_blocked: EventWriter<'w, HitBlocked>,
+}
+
+impl EnemyVulnerability<'_, '_> {
+    /// Returns `false` if the hit was blocked, in which case `HitBlocked` is sent.
+    pub fn hit(&mut self, enemy: Entity, source: HitSource, hit_pos: Vec2) -> bool {
+        if let Ok(boss) = self.q_bosses.get(enemy) {
+            // Hooking the boss is what opens its weak point.
+            if source == HitSource::Hook || boss.exposed() {
+                return true;
+            }
+            self.ev_hit_blocked.send(HitBlocked {
+                enemy,
+                source,
+                pos: hit_pos,
+            });
+            return false;
+        }
+
+        let (transform, shield) = match self.q_shields.get(enemy) {
+            Ok(r) => r,
+            Err(_) => return true,
+        };
+
+        let enemy_pos = transform.translation().truncate();
+        if !shield.blocks(source, enemy_pos, hit_pos) {
+            return true;
+        }
+
+        self.ev_hit_blocked.send(HitBlocked {
+            enemy,
+            source,
+            pos: shield.impact_pos(enemy_pos),
+        });
+        false
+    }
+}
+
+fn play_clang_sounds(
+    assets: Res<GameAssets>,
+    mut ev_hit_blocked: EventReader<HitBlocked>,
+    mut ev_play_sound: EventWriter<PlaySound>,
+) {
+    for _ in ev_hit_blocked.read() {
+        ev_play_sound.send(PlaySound {
+            clip: assets.strike_sound.clone(),
+            volume: CLANG_VOLUME,
+            playback_rate: CLANG_PLAYBACK_RATE,
+            ..default()
+        });
+    }
+}
+
+pub struct EnemyVulnerabilityPlugin;
+
+impl Plugin for EnemyVulnerabilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_tick_event::<HitBlocked>()
+            .add_systems(Update, (play_clang_sounds,).in_set(GameplaySet));
+    }
+}
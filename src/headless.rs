@@ -0,0 +1,106 @@
+use std::time::{Duration, Instant};
+
+use bevy::app::{PluginGroupBuilder, PluginsState, ScheduleRunnerPlugin};
+use bevy::diagnostic::DiagnosticsPlugin;
+use bevy::gizmos::GizmoPlugin;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::render::{settings::WgpuSettings, RenderPlugin};
+use bevy::scene::ScenePlugin;
+use bevy::sprite::SpritePlugin;
+use bevy::text::TextPlugin;
+use bevy::ui::UiPlugin;
+use bevy::window::ExitCondition;
+
+use crate::player::{kill_counter::KillCounter, score::PlayerScore, speed_timer::SpeedTimer};
+use crate::GameState;
+
+const ASSET_LOADING_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The outcome of a run, the same values that get posted to the leaderboard.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunSummary {
+    pub score: u32,
+    pub kills: u32,
+    pub time: f32,
+}
+
+impl RunSummary {
+    pub fn from_world(world: &World) -> Self {
+        Self {
+            score: world.resource::<PlayerScore>().score(),
+            kills: world.resource::<KillCounter>().kills(),
+            time: world.resource::<SpeedTimer>().elapsed,
+        }
+    }
+}
+
+/// The plugins the game needs, without a window, GPU, audio device or event loop.
+/// The app updates as fast as it can, which with `FrameStepping` means
+/// the game runs a lot faster than real time.
+///
+/// `RenderPlugin` has no backends, so there is no render app,
+/// it only sets up the cameras, visibility and the render assets the game loads.
+pub fn headless_plugins() -> PluginGroupBuilder {
+    MinimalPlugins
+        .set(ScheduleRunnerPlugin::run_loop(Duration::ZERO))
+        .add(TransformPlugin)
+        .add(HierarchyPlugin)
+        .add(DiagnosticsPlugin)
+        .add(InputPlugin)
+        .add(WindowPlugin {
+            primary_window: None,
+            exit_condition: ExitCondition::DontExit,
+            ..default()
+        })
+        .add(AssetPlugin::default())
+        .add(ScenePlugin)
+        .add(RenderPlugin {
+            render_creation: WgpuSettings {
+                backends: None,
+                ..default()
+            }
+            .into(),
+        })
+        .add(ImagePlugin::default_nearest())
+        .add(SpritePlugin)
+        .add(TextPlugin)
+        .add(UiPlugin)
+        .add(GizmoPlugin)
+}
+
+pub fn game_state(app: &App) -> GameState {
+    app.world.resource::<State<GameState>>().get().clone()
+}
+
+/// Finish building the app, for when we call `app.update()` ourselves instead of `app.run()`.
+pub fn finish_plugins(app: &mut App) {
+    while app.plugins_state() == PluginsState::Adding {
+        #[cfg(not(target_arch = "wasm32"))]
+        bevy::tasks::tick_global_task_pools_on_main_thread();
+    }
+    app.finish();
+    app.cleanup();
+}
+
+/// Finish building the app, load all assets and skip the main menu.
+/// Returns `false` if the assets didn't load in time.
+pub fn prepare_app(app: &mut App) -> bool {
+    finish_plugins(app);
+
+    let start = Instant::now();
+    while game_state(app) == GameState::AssetLoading {
+        if start.elapsed() > ASSET_LOADING_TIMEOUT {
+            return false;
+        }
+        app.update();
+    }
+
+    if game_state(app) == GameState::MainMenu {
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Gaming);
+        app.update();
+    }
+    true
+}
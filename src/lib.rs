@@ -0,0 +1,102 @@
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
+pub mod collision;
+pub mod enemy;
+pub mod headless;
+pub mod player;
+pub mod protocol;
+pub mod replay;
+pub mod settings;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod utils;
+pub mod world;
+
+mod assets;
+mod audio;
+mod effect;
+mod ui;
+
+pub use assets::GameAssets;
+
+use bevy::prelude::*;
+
+use bevy_asset_loader::prelude::*;
+use bevy_mod_reqwest::ReqwestPlugin;
+use bevy_rapier2d::prelude::*;
+use bevy_trickfilm::Animation2DPlugin;
+
+const BACKGROUND_COLOR: Color = Color::rgb(0.75, 0.6, 0.5);
+
+#[derive(States, Clone, Eq, PartialEq, Debug, Hash, Default)]
+pub enum GameState {
+    #[default]
+    AssetLoading,
+    MainMenu,
+    Gaming,
+    GameOver,
+    Restart,
+}
+
+/// A sub-state of `GameState::Gaming`, it's reset to `Running` whenever the run ends.
+/// Gameplay systems, physics and the `SpeedTimer` don't run while `Paused`.
+#[derive(States, Clone, Eq, PartialEq, Debug, Hash, Default)]
+pub enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+/// Systems that only run in `GameState::Gaming` while the game isn't paused.
+/// The gameplay itself runs in `FixedUpdate`, see `world::determinism::TickSet`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameplaySet;
+
+/// Everything that makes up the game, expects `DefaultPlugins` (or `headless::headless_plugins`)
+/// to be added already.
+///
+/// `SeedOverride` and `ReplayPlayback` are read when the plugin is built,
+/// so they have to be inserted before it.
+/// Replays start right away, everything else starts in the main menu.
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        let first_state = if app.world.contains_resource::<replay::ReplayPlayback>() {
+            GameState::Gaming
+        } else {
+            GameState::MainMenu
+        };
+
+        app.add_plugins((
+            RapierPhysicsPlugin::<NoUserData>::default().in_fixed_schedule(),
+            RapierDebugRenderPlugin {
+                enabled: false,
+                ..default()
+            },
+            Animation2DPlugin,
+            ReqwestPlugin,
+        ))
+        .insert_resource(Msaa::Off)
+        .add_state::<GameState>()
+        .add_loading_state(
+            LoadingState::new(GameState::AssetLoading).continue_to_state(first_state),
+        )
+        .configure_loading_state(
+            LoadingStateConfig::new(GameState::AssetLoading).load_collection::<GameAssets>(),
+        )
+        .add_plugins((
+            settings::SettingsPlugin,
+            audio::GameAudioPlugin,
+            world::WorldPlugin,
+            ui::UiPlugin,
+            effect::EffectPlugin,
+            player::PlayerPlugin,
+            enemy::EnemyPlugin,
+            utils::UtilsPlugin,
+            replay::ReplayPlugin,
+            collision::CollisionRouterPlugin,
+        ))
+        .insert_resource(ClearColor(BACKGROUND_COLOR));
+    }
+}
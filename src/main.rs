@@ -1,45 +1,39 @@
-#![allow(clippy::too_many_arguments, clippy::type_complexity)]
-
-mod assets;
-mod audio;
-mod effect;
-mod enemy;
-mod player;
-mod ui;
-mod utils;
-mod world;
-
-pub use assets::GameAssets;
-
 use bevy::asset::AssetMetaCheck;
 use bevy::prelude::*;
-use bevy::window::{PresentMode, Window, WindowMode};
+use bevy::window::{PresentMode, Window};
 
-use bevy_asset_loader::prelude::*;
-use bevy_mod_reqwest::ReqwestPlugin;
-use bevy_rapier2d::prelude::*;
-use bevy_trickfilm::Animation2DPlugin;
+use insta_kill::replay::ReplayPlayback;
+use insta_kill::settings::Settings;
+use insta_kill::world::{daily::GameMode, seed::SeedOverride};
+use insta_kill::GamePlugin;
 
-const BACKGROUND_COLOR: Color = Color::rgb(0.75, 0.6, 0.5);
+fn main() {
+    let mut app = App::new();
 
-#[derive(States, Clone, Eq, PartialEq, Debug, Hash, Default)]
-pub enum GameState {
-    #[default]
-    AssetLoading,
-    Gaming,
-    GameOver,
-    Restart,
-}
+    // A replay is only valid with the seed it was recorded with.
+    match ReplayPlayback::from_args() {
+        Some(playback) => {
+            app.insert_resource(SeedOverride(Some(playback.seed())))
+                .insert_resource(playback);
+        }
+        None => {
+            app.insert_resource(SeedOverride::from_args())
+                .insert_resource(GameMode::from_args());
+        }
+    }
 
-fn main() {
-    App::new()
-        .insert_resource(AssetMetaCheck::Never)
+    // The window is created before the game loads the settings.
+    let settings = Settings::load();
+    let mode = settings.window_mode();
+
+    app.insert_resource(AssetMetaCheck::Never)
+        .insert_resource(settings)
         .add_plugins((
             DefaultPlugins
                 .set(WindowPlugin {
                     primary_window: Some(Window {
                         present_mode: PresentMode::Fifo,
-                        mode: WindowMode::Windowed,
+                        mode,
                         fit_canvas_to_parent: true,
                         ..default()
                     }),
@@ -47,31 +41,7 @@ fn main() {
                 })
                 .set(ImagePlugin::default_nearest())
                 .build(),
-            RapierPhysicsPlugin::<NoUserData>::default(),
-            RapierDebugRenderPlugin {
-                enabled: false,
-                ..default()
-            },
-            Animation2DPlugin,
-            ReqwestPlugin,
-        ))
-        .insert_resource(Msaa::Off)
-        .add_state::<GameState>()
-        .add_loading_state(
-            LoadingState::new(GameState::AssetLoading).continue_to_state(GameState::Gaming),
-        )
-        .configure_loading_state(
-            LoadingStateConfig::new(GameState::AssetLoading).load_collection::<GameAssets>(),
-        )
-        .add_plugins((
-            audio::GameAudioPlugin,
-            world::WorldPlugin,
-            ui::UiPlugin,
-            effect::EffectPlugin,
-            player::PlayerPlugin,
-            enemy::EnemyPlugin,
-            utils::UtilsPlugin,
+            GamePlugin,
         ))
-        .insert_resource(ClearColor(BACKGROUND_COLOR))
         .run();
 }
@@ -1,50 +1,34 @@
 use bevy::prelude::*;
-use bevy_rapier2d::prelude::*;
 
 use crate::{
-    enemy::{Enemy, EnemyProjectile},
-    GameState,
+    collision::{CollisionRouterSet, HookAttached, PlayerHit},
+    enemy::{Enemy, EnemyProjectile, EnemyVulnerability, HitSource},
+    GameplaySet,
 };
 
-use super::{hook::PlayerHook, state::PlayerState, Player};
+use super::{state::PlayerState, Player};
 
-fn enemy_collisions(
+fn player_hits(
     mut q_player: Query<&mut Player>,
     q_enemies: Query<&Enemy>,
-    q_colliders: Query<&Parent, (With<Collider>, Without<Enemy>, Without<Player>)>,
-    mut ev_collision_events: EventReader<CollisionEvent>,
+    q_enemy_projectiles: Query<&EnemyProjectile>,
+    mut ev_player_hit: EventReader<PlayerHit>,
 ) {
     let mut player = match q_player.get_single_mut() {
         Ok(r) => r,
         Err(_) => return,
     };
 
-    for ev in ev_collision_events.read() {
-        let (source, target) = match ev {
-            CollisionEvent::Started(source, target, _) => (source, target),
-            CollisionEvent::Stopped(_, _, _) => continue,
-        };
-
-        let enemy_parent = if &player.collider_entity == source {
-            match q_colliders.get(*target) {
-                Ok(parent) => parent,
-                Err(_) => continue,
-            }
-        } else if &player.collider_entity == target {
-            match q_colliders.get(*source) {
-                Ok(parent) => parent,
-                Err(_) => continue,
-            }
+    for ev in ev_player_hit.read() {
+        let disabled = if let Ok(enemy) = q_enemies.get(ev.by) {
+            enemy.disabled
+        } else if let Ok(projectile) = q_enemy_projectiles.get(ev.by) {
+            projectile.disabled
         } else {
             continue;
         };
 
-        let enemy = match q_enemies.get(enemy_parent.get()) {
-            Ok(r) => r,
-            Err(_) => continue,
-        };
-
-        if enemy.disabled {
+        if disabled {
             continue;
         }
 
@@ -52,99 +36,37 @@ fn enemy_collisions(
     }
 }
 
-fn enemy_projectile_collisions(
+fn attach_hooks(
     mut q_player: Query<&mut Player>,
-    q_enemy_projectiles: Query<&EnemyProjectile>,
-    q_colliders: Query<&Parent, (With<Collider>, Without<EnemyProjectile>, Without<Player>)>,
-    mut ev_collision_events: EventReader<CollisionEvent>,
+    mut q_enemies: Query<(&Transform, &mut Enemy)>,
+    mut vulnerability: EnemyVulnerability,
+    mut ev_hook_attached: EventReader<HookAttached>,
 ) {
     let mut player = match q_player.get_single_mut() {
         Ok(r) => r,
         Err(_) => return,
     };
 
-    for ev in ev_collision_events.read() {
-        let (source, target) = match ev {
-            CollisionEvent::Started(source, target, _) => (source, target),
-            CollisionEvent::Stopped(_, _, _) => continue,
-        };
-
-        let enemy_parent = if &player.collider_entity == source {
-            match q_colliders.get(*target) {
-                Ok(parent) => parent,
-                Err(_) => continue,
-            }
-        } else if &player.collider_entity == target {
-            match q_colliders.get(*source) {
-                Ok(parent) => parent,
-                Err(_) => continue,
-            }
-        } else {
+    for ev in ev_hook_attached.read() {
+        if player.state == PlayerState::Dashing {
             continue;
-        };
-
-        let projectile = match q_enemy_projectiles.get(enemy_parent.get()) {
-            Ok(r) => r,
-            Err(_) => continue,
-        };
-
-        if projectile.disabled {
+        }
+        // We already hooked an enemy, ignore any further collisions.
+        if player.hook_target_pos != Vec2::ZERO {
             continue;
         }
 
-        player.disabled = true;
-    }
-}
-
-fn hook_enemy_collision(
-    mut q_player: Query<&mut Player>,
-    q_hooks: Query<&PlayerHook>,
-    mut q_enemies: Query<(&Transform, &mut Enemy)>,
-    q_colliders: Query<&Parent, (With<Collider>, Without<Enemy>, Without<Player>)>,
-    mut ev_collision_events: EventReader<CollisionEvent>,
-) {
-    let mut player = match q_player.get_single_mut() {
-        Ok(r) => r,
-        Err(_) => return,
-    };
-    if player.state == PlayerState::Dashing {
-        return;
-    }
-    // We already hooked an enemy, don't check for any further collisions.
-    if player.hook_target_pos != Vec2::ZERO {
-        return;
-    }
-
-    for ev in ev_collision_events.read() {
-        let (source, target) = match ev {
-            CollisionEvent::Started(source, target, _) => (source, target),
-            CollisionEvent::Stopped(_, _, _) => continue,
-        };
-
-        let source_parent = match q_colliders.get(*source) {
-            Ok(p) => p.get(),
-            Err(_) => continue,
-        };
-        let target_parent = match q_colliders.get(*target) {
-            Ok(p) => p.get(),
+        let (enemy_transform, mut enemy) = match q_enemies.get_mut(ev.enemy) {
+            Ok(r) => r,
             Err(_) => continue,
         };
 
-        let (enemy_transform, mut enemy) = if let Ok(r) = q_enemies.get_mut(source_parent) {
-            r
-        } else if let Ok(r) = q_enemies.get_mut(target_parent) {
-            r
-        } else {
+        if enemy.hook_immune {
             continue;
-        };
-
-        let _ = if let Ok(r) = q_hooks.get(source_parent) {
-            r
-        } else if let Ok(r) = q_hooks.get(target_parent) {
-            r
-        } else {
+        }
+        if !vulnerability.hit(ev.enemy, HitSource::Hook, ev.pos) {
             continue;
-        };
+        }
 
         player.state = PlayerState::Sliding;
         player.hook_target_pos = enemy_transform.translation.truncate();
@@ -157,13 +79,10 @@ pub struct PlayerCollisionPlugin;
 impl Plugin for PlayerCollisionPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
-            (
-                enemy_collisions,
-                enemy_projectile_collisions,
-                hook_enemy_collision,
-            )
-                .run_if(in_state(GameState::Gaming)),
+            FixedUpdate,
+            (player_hits, attach_hooks)
+                .after(CollisionRouterSet)
+                .in_set(GameplaySet),
         );
     }
 }
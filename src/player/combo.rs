@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{enemy::HitSource, GameplaySet};
+
+/// How long the combo lasts after the last kill.
+const COMBO_WINDOW: f32 = 2.5;
+/// Every this many combo points the multiplier goes up by `MULTIPLIER_STEP`.
+const POINTS_PER_STEP: f32 = 5.0;
+const MULTIPLIER_STEP: f32 = 0.5;
+const MAX_MULTIPLIER: f32 = 4.0;
+
+/// Kills of the player within `COMBO_WINDOW` of each other raise the combo and with it
+/// the score multiplier. Once the combo runs out, the multiplier drops by one step every window.
+#[derive(Resource)]
+pub struct Combo {
+    count: u32,
+    points: f32,
+    timer: Timer,
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        let mut timer = Timer::from_seconds(COMBO_WINDOW, TimerMode::Once);
+        timer.set_elapsed(Duration::from_secs_f32(COMBO_WINDOW));
+        Self {
+            count: 0,
+            points: 0.0,
+            timer,
+        }
+    }
+}
+
+impl Combo {
+    /// How many combo points a kill with the given source is worth,
+    /// `None` if the player didn't make the kill.
+    fn points(source: Option<HitSource>) -> Option<f32> {
+        match source? {
+            HitSource::Strike => Some(1.0),
+            HitSource::Reflection => Some(1.5),
+            HitSource::Dash | HitSource::DashLanding => Some(0.5),
+            HitSource::Hook => Some(0.0),
+            HitSource::Charge => None,
+        }
+    }
+
+    /// Count the kill towards the combo and return the score multiplied by the new multiplier.
+    /// Kills that the player didn't make keep their score and don't count.
+    pub fn register_kill(&mut self, source: Option<HitSource>, score: u32) -> u32 {
+        let points = match Self::points(source) {
+            Some(r) => r,
+            None => return score,
+        };
+
+        self.count += 1;
+        self.points += points;
+        self.timer.reset();
+        (score as f32 * self.multiplier()).round() as u32
+    }
+
+    /// End the combo and drop the multiplier by one step,
+    /// the timer restarts until the multiplier is back at `1.0`.
+    fn step_down(&mut self) {
+        self.count = 0;
+        let steps = ((self.multiplier() - 1.0) / MULTIPLIER_STEP).round();
+        self.points = (steps - 1.0).max(0.0) * POINTS_PER_STEP;
+        if self.points > 0.0 {
+            self.timer.reset();
+        }
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    pub fn multiplier(&self) -> f32 {
+        (1.0 + (self.points / POINTS_PER_STEP).floor() * MULTIPLIER_STEP).min(MAX_MULTIPLIER)
+    }
+
+    /// How much of the combo window is left, `0.0` once the combo ran out.
+    pub fn remaining(&self) -> f32 {
+        self.timer.percent_left()
+    }
+
+    pub fn active(&self) -> bool {
+        !self.timer.finished()
+    }
+}
+
+fn tick_combo(time: Res<Time>, mut combo: ResMut<Combo>) {
+    combo.timer.tick(time.delta());
+    if combo.timer.just_finished() {
+        combo.step_down();
+    }
+}
+
+pub struct PlayerComboPlugin;
+
+impl Plugin for PlayerComboPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Combo>()
+            .add_systems(FixedUpdate, (tick_combo,).in_set(GameplaySet));
+    }
+}
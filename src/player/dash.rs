@@ -8,7 +8,7 @@ use crate::{
     effect::super_sonic::SpawnSuperSonic,
     utils::{quat_from_vec2, COLLISION_GROUPS_NONE},
     world::camera::YSort,
-    GameAssets, GameState,
+    GameAssets, GameplaySet,
 };
 
 use super::{
@@ -69,6 +69,7 @@ fn trigger_dash(
 }
 
 fn move_player(
+    player_input: Res<PlayerInput>,
     mouse_coords: Res<MouseWorldCoords>,
     mut q_player: Query<(&mut Transform, &mut Velocity), With<Player>>,
     mut ev_player_changed_state: EventReader<PlayerChangedState>,
@@ -103,12 +104,12 @@ fn move_player(
             });
             (dir, 2.0)
         } else {
-            let dir = (mouse_coords.0 - transform.translation.truncate()).normalize_or_zero();
+            let dir = player_input.aim_direction(&mouse_coords, transform.translation.truncate());
             (dir, 1.0)
         };
 
         let dir = if dir == Vec2::ZERO {
-            (mouse_coords.0 - transform.translation.truncate()).normalize_or_zero()
+            player_input.aim_direction(&mouse_coords, transform.translation.truncate())
         } else {
             dir
         };
@@ -301,7 +302,7 @@ impl Plugin for PlayerDashPlugin {
         dash_timer.set_elapsed(dash_timer.duration());
 
         app.add_systems(
-            Update,
+            FixedUpdate,
             (
                 trigger_dash,
                 move_player,
@@ -315,7 +316,7 @@ impl Plugin for PlayerDashPlugin {
                 animate_dash_refresh,
             )
                 .chain()
-                .run_if(in_state(GameState::Gaming)),
+                .in_set(GameplaySet),
         )
         .insert_resource(DashTimer(dash_timer));
     }
@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use bevy_trickfilm::prelude::*;
 
-use crate::{utils::quat_from_vec2, world::camera::YSort, GameAssets, GameState};
+use crate::{utils::quat_from_vec2, world::camera::YSort, GameAssets, GameplaySet};
 
 use super::{
     input::{MouseWorldCoords, PlayerInput},
@@ -41,6 +41,7 @@ fn trigger_hook(player_input: Res<PlayerInput>, mut q_player: Query<&mut Player>
 fn spawn_hooks(
     mut commands: Commands,
     assets: Res<GameAssets>,
+    player_input: Res<PlayerInput>,
     mouse_coords: Res<MouseWorldCoords>,
     mut q_player: Query<(Entity, &Transform, &mut TextureAtlasSprite), With<Player>>,
     mut ev_player_changed_state: EventReader<PlayerChangedState>,
@@ -58,7 +59,7 @@ fn spawn_hooks(
         let mut animator = AnimationPlayer2D::default();
         animator.play(assets.player_hook_animations[0].clone());
 
-        let dir = (mouse_coords.0 - transform.translation.truncate()).normalize_or_zero();
+        let dir = player_input.aim_direction(&mouse_coords, transform.translation.truncate());
         let rot = quat_from_vec2(dir);
         let transform =
             Transform::from_translation(rot.mul_vec3(ROT_OFFSET) + OFFSET).with_rotation(rot);
@@ -142,7 +143,7 @@ pub struct PlayerHookPlugin;
 impl Plugin for PlayerHookPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
+            FixedUpdate,
             (
                 trigger_hook,
                 spawn_hooks,
@@ -150,7 +151,7 @@ impl Plugin for PlayerHookPlugin {
                 reverse_animations,
                 move_hook_colliders,
             )
-                .run_if(in_state(GameState::Gaming)),
+                .in_set(GameplaySet),
         );
     }
 }
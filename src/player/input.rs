@@ -1,17 +1,36 @@
-use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
 use bevy::input::InputSystem;
 use bevy::prelude::*;
 use bevy::window::{PrimaryWindow, Window};
 
+use crate::world::determinism::TickSet;
 use crate::world::world_debug::DebugMode;
 use crate::world::MainCamera;
 
+use super::input_map::{ActionInput, InputAction, InputMapPlugin};
+
+const STICK_DEADZONE: f32 = 0.2;
+
+/// The systems that fill `PlayerInput` from the live input devices.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PlayerInputSet;
+
 #[derive(Resource, Default)]
 pub struct MouseWorldCoords(pub Vec2);
 
+/// The device the player used last, decides whether we aim with the cursor or the right stick.
+#[derive(Resource, Default, PartialEq, Clone, Copy)]
+pub enum InputDevice {
+    #[default]
+    KeyboardMouse,
+    Gamepad,
+}
+
 #[derive(Resource, Default)]
 pub struct PlayerInput {
     pub move_direction: Vec2,
+    /// Aim direction from the gamepad, `None` if we should aim with the cursor.
+    pub aim_stick: Option<Vec2>,
     pub attack: bool,
     pub dash: bool,
     pub hook: bool,
@@ -19,10 +38,61 @@ pub struct PlayerInput {
     pub escape: bool,
     pub toggle_fullscreen: bool,
     pub restart: bool,
+    pub switch_tab: bool,
+    pub previous_page: bool,
+    pub next_page: bool,
+    pub switch_filter: bool,
+    pub jump_to_rank: bool,
+    pub menu_up: bool,
+    pub menu_down: bool,
+    pub menu_left: bool,
+    pub menu_right: bool,
+    pub confirm: bool,
 }
 
+impl PlayerInput {
+    /// The normalized direction the player is aiming at, seen from `origin`.
+    pub fn aim_direction(&self, mouse_coords: &MouseWorldCoords, origin: Vec2) -> Vec2 {
+        match self.aim_stick {
+            Some(dir) => dir,
+            None => (mouse_coords.0 - origin).normalize_or_zero(),
+        }
+    }
+
+    pub fn consume_actions(&mut self) {
+        self.attack = false;
+        self.dash = false;
+        self.hook = false;
+    }
+}
+
+/// The gameplay actions are kept until a tick used them, there may be frames without a tick.
 fn reset_player_input(mut player_input: ResMut<PlayerInput>) {
-    *player_input = PlayerInput::default();
+    *player_input = PlayerInput {
+        attack: player_input.attack,
+        dash: player_input.dash,
+        hook: player_input.hook,
+        ..default()
+    };
+}
+
+fn consume_actions(mut player_input: ResMut<PlayerInput>) {
+    player_input.consume_actions();
+}
+
+fn stick_input(
+    gamepads: &Gamepads,
+    axes: &Axis<GamepadAxis>,
+    x_axis: GamepadAxisType,
+    y_axis: GamepadAxisType,
+) -> Option<Vec2> {
+    gamepads.iter().find_map(|gamepad| {
+        let stick = Vec2::new(
+            axes.get(GamepadAxis::new(gamepad, x_axis))?,
+            axes.get(GamepadAxis::new(gamepad, y_axis))?,
+        );
+        (stick.length_squared() >= STICK_DEADZONE.powi(2)).then_some(stick)
+    })
 }
 
 pub fn fetch_mouse_world_coords(
@@ -73,66 +143,148 @@ fn fetch_scroll_events(
     }
 }
 
-fn player_movement(keys: Res<Input<KeyCode>>, mut player_input: ResMut<PlayerInput>) {
+fn player_movement(
+    actions: ActionInput,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut player_input: ResMut<PlayerInput>,
+) {
     let mut direction = Vec2::default();
 
-    if keys.pressed(KeyCode::J) || keys.pressed(KeyCode::S) {
+    if actions.pressed(InputAction::MoveDown) {
         direction += Vec2::new(0.0, -1.0);
     }
-    if keys.pressed(KeyCode::K) || keys.pressed(KeyCode::W) {
+    if actions.pressed(InputAction::MoveUp) {
         direction += Vec2::new(0.0, 1.0);
     }
-    if keys.pressed(KeyCode::F) || keys.pressed(KeyCode::D) {
+    if actions.pressed(InputAction::MoveRight) {
         direction += Vec2::new(1.0, 0.0);
     }
-    if keys.pressed(KeyCode::A) {
+    if actions.pressed(InputAction::MoveLeft) {
         direction += Vec2::new(-1.0, 0.0);
     }
 
-    player_input.move_direction = direction.normalize_or_zero();
-}
+    if direction != Vec2::ZERO {
+        player_input.move_direction = direction.normalize_or_zero();
+        return;
+    }
 
-fn attack(mouse_buttons: Res<Input<MouseButton>>, mut player_input: ResMut<PlayerInput>) {
-    let pressed = mouse_buttons.just_pressed(MouseButton::Left);
-    player_input.attack = pressed;
+    // Analog movement, we don't normalize so that the player can walk slowly.
+    if let Some(stick) = stick_input(
+        &gamepads,
+        &axes,
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+    ) {
+        player_input.move_direction = stick.clamp_length_max(1.0);
+    }
 }
 
-fn dash(keys: Res<Input<KeyCode>>, mut player_input: ResMut<PlayerInput>) {
-    let pressed = keys.just_pressed(KeyCode::Space);
-    player_input.dash = pressed;
-}
+fn gamepad_aim(
+    input_device: Res<InputDevice>,
+    gamepads: Res<Gamepads>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut player_input: ResMut<PlayerInput>,
+    mut last_aim: Local<Vec2>,
+) {
+    if *input_device != InputDevice::Gamepad {
+        return;
+    }
 
-fn hook(keys: Res<Input<KeyCode>>, mut player_input: ResMut<PlayerInput>) {
-    let pressed = keys.just_pressed(KeyCode::ShiftLeft);
-    player_input.hook = pressed;
-}
+    // Prefer the right stick, fall back to the movement direction
+    // and keep the last aim if neither is used.
+    if let Some(stick) = stick_input(
+        &gamepads,
+        &axes,
+        GamepadAxisType::RightStickX,
+        GamepadAxisType::RightStickY,
+    ) {
+        *last_aim = stick.normalize_or_zero();
+    } else if player_input.move_direction != Vec2::ZERO {
+        *last_aim = player_input.move_direction.normalize_or_zero();
+    }
 
-fn input_escape(keys: Res<Input<KeyCode>>, mut player_input: ResMut<PlayerInput>) {
-    player_input.escape = keys.just_pressed(KeyCode::Escape);
+    if *last_aim == Vec2::ZERO {
+        *last_aim = Vec2::X;
+    }
+    player_input.aim_stick = Some(*last_aim);
 }
 
-fn toggle_fullscreen(
+fn detect_input_device(
     keys: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
     gamepads: Res<Gamepads>,
-    button_inputs: Res<Input<GamepadButton>>,
-    mut player_input: ResMut<PlayerInput>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut ev_mouse_motion: EventReader<MouseMotion>,
+    mut input_device: ResMut<InputDevice>,
 ) {
-    let mut pressed = keys.just_pressed(KeyCode::B);
-    for gamepad in gamepads.iter() {
-        if button_inputs.just_pressed(GamepadButton::new(gamepad, GamepadButtonType::DPadUp)) {
-            pressed = true;
-        }
+    let mouse_moved = ev_mouse_motion.read().count() > 0;
+    if mouse_moved
+        || keys.get_just_pressed().next().is_some()
+        || mouse_buttons.get_just_pressed().next().is_some()
+    {
+        input_device.set_if_neq(InputDevice::KeyboardMouse);
+        return;
     }
 
-    player_input.toggle_fullscreen = pressed;
+    let stick_moved = [
+        (GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY),
+        (GamepadAxisType::RightStickX, GamepadAxisType::RightStickY),
+    ]
+    .into_iter()
+    .any(|(x_axis, y_axis)| stick_input(&gamepads, &axes, x_axis, y_axis).is_some());
+
+    if stick_moved || gamepad_buttons.get_just_pressed().next().is_some() {
+        input_device.set_if_neq(InputDevice::Gamepad);
+    }
+}
+
+fn attack(actions: ActionInput, mut player_input: ResMut<PlayerInput>) {
+    player_input.attack |= actions.just_pressed(InputAction::Attack);
+}
+
+fn dash(actions: ActionInput, mut player_input: ResMut<PlayerInput>) {
+    player_input.dash |= actions.just_pressed(InputAction::Dash);
+}
+
+fn hook(actions: ActionInput, mut player_input: ResMut<PlayerInput>) {
+    player_input.hook |= actions.just_pressed(InputAction::Hook);
+}
+
+fn input_escape(actions: ActionInput, mut player_input: ResMut<PlayerInput>) {
+    player_input.escape = actions.just_pressed(InputAction::Escape);
+}
+
+fn toggle_fullscreen(actions: ActionInput, mut player_input: ResMut<PlayerInput>) {
+    player_input.toggle_fullscreen = actions.just_pressed(InputAction::ToggleFullscreen);
+}
+
+fn restart(actions: ActionInput, mut player_input: ResMut<PlayerInput>) {
+    player_input.restart = actions.just_pressed(InputAction::Restart);
+}
+
+fn switch_tab(actions: ActionInput, mut player_input: ResMut<PlayerInput>) {
+    player_input.switch_tab = actions.just_pressed(InputAction::SwitchTab);
+}
+
+fn leaderboard_navigation(actions: ActionInput, mut player_input: ResMut<PlayerInput>) {
+    player_input.previous_page = actions.just_pressed(InputAction::PreviousPage);
+    player_input.next_page = actions.just_pressed(InputAction::NextPage);
+    player_input.switch_filter = actions.just_pressed(InputAction::SwitchFilter);
+    player_input.jump_to_rank = actions.just_pressed(InputAction::JumpToRank);
 }
 
-fn restart(keys: Res<Input<KeyCode>>, mut player_input: ResMut<PlayerInput>) {
-    player_input.restart = keys.just_pressed(KeyCode::R);
+fn menu_navigation(actions: ActionInput, mut player_input: ResMut<PlayerInput>) {
+    player_input.menu_up = actions.just_pressed(InputAction::MenuUp);
+    player_input.menu_down = actions.just_pressed(InputAction::MenuDown);
+    player_input.menu_left = actions.just_pressed(InputAction::MenuLeft);
+    player_input.menu_right = actions.just_pressed(InputAction::MenuRight);
+    player_input.confirm = actions.just_pressed(InputAction::Confirm);
 }
 
-fn toggle_debug_mode(keys: Res<Input<KeyCode>>, mut debug_mode: ResMut<DebugMode>) {
-    if keys.just_pressed(KeyCode::F1) {
+fn toggle_debug_mode(actions: ActionInput, mut debug_mode: ResMut<DebugMode>) {
+    if actions.just_pressed(InputAction::ToggleDebug) {
         debug_mode.active = !debug_mode.active;
     }
 }
@@ -141,24 +293,35 @@ pub struct InputPlugin;
 
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            PreUpdate,
-            (
-                fetch_scroll_events,
-                fetch_mouse_world_coords,
-                player_movement,
-                attack,
-                dash,
-                hook,
-                input_escape,
-                toggle_fullscreen,
-                restart,
-                toggle_debug_mode,
+        app.add_plugins(InputMapPlugin)
+            .add_systems(
+                PreUpdate,
+                (
+                    fetch_scroll_events,
+                    fetch_mouse_world_coords,
+                    detect_input_device,
+                    player_movement,
+                    gamepad_aim
+                        .after(detect_input_device)
+                        .after(player_movement),
+                    attack,
+                    dash,
+                    hook,
+                    input_escape,
+                    toggle_fullscreen,
+                    restart,
+                    switch_tab,
+                    leaderboard_navigation,
+                    menu_navigation,
+                    toggle_debug_mode,
+                )
+                    .in_set(PlayerInputSet)
+                    .after(InputSystem),
             )
-                .after(InputSystem),
-        )
-        .init_resource::<PlayerInput>()
-        .init_resource::<MouseWorldCoords>()
-        .add_systems(PreUpdate, reset_player_input.before(InputSystem));
+            .init_resource::<PlayerInput>()
+            .init_resource::<InputDevice>()
+            .init_resource::<MouseWorldCoords>()
+            .add_systems(PreUpdate, reset_player_input.before(InputSystem))
+            .add_systems(FixedUpdate, consume_actions.in_set(TickSet::Finish));
     }
 }
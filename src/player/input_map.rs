@@ -0,0 +1,364 @@
+use std::collections::BTreeMap;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::utils::storage;
+
+/// The key of the input map from before it was part of the `Settings`.
+pub const STORAGE_KEY: &str = "input_map";
+/// Maps without a version were stored in full, with the defaults of the time.
+const STORAGE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Attack,
+    Dash,
+    Hook,
+    Escape,
+    ToggleFullscreen,
+    Restart,
+    SwitchTab,
+    PreviousPage,
+    NextPage,
+    SwitchFilter,
+    JumpToRank,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+    Confirm,
+    ToggleDebug,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButtonType),
+}
+
+impl InputBinding {
+    pub fn is_gamepad(&self) -> bool {
+        matches!(self, InputBinding::Gamepad(_))
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            InputBinding::Key(key) => format!("{:?}", key),
+            InputBinding::Mouse(button) => format!("MOUSE {:?}", button),
+            InputBinding::Gamepad(button) => format!("{:?}", button),
+        }
+        .to_uppercase()
+    }
+}
+
+/// Maps every `InputAction` to the bindings that trigger it.
+/// Any of the bindings being pressed counts as the action being pressed.
+///
+/// Only the actions that differ from the defaults are stored,
+/// so that changed defaults still reach players that rebound something else.
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(from = "StoredInputMap", into = "StoredInputMap")]
+pub struct InputMap {
+    bindings: BTreeMap<InputAction, Vec<InputBinding>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredInputMap {
+    #[serde(default)]
+    version: u32,
+    bindings: BTreeMap<InputAction, Vec<InputBinding>>,
+}
+
+/// Bindings that used to be the default. Unversioned maps contain them even though the player
+/// never chose them, so they are dropped in favour of the current defaults.
+fn is_stale_default(action: InputAction, bindings: &[InputBinding]) -> bool {
+    let stale: &[InputBinding] = match action {
+        InputAction::Attack => &[InputBinding::Mouse(MouseButton::Left)],
+        InputAction::Dash => &[InputBinding::Key(KeyCode::Space)],
+        InputAction::Hook => &[InputBinding::Key(KeyCode::ShiftLeft)],
+        InputAction::Escape => &[InputBinding::Key(KeyCode::Escape)],
+        InputAction::Restart => &[InputBinding::Key(KeyCode::R)],
+        // The up of the d-pad navigates the menus, where fullscreen can be toggled too.
+        InputAction::ToggleFullscreen => &[
+            InputBinding::Key(KeyCode::B),
+            InputBinding::Gamepad(GamepadButtonType::DPadUp),
+        ],
+        // The page buttons took over the right of the d-pad.
+        InputAction::SwitchTab => &[
+            InputBinding::Key(KeyCode::Tab),
+            InputBinding::Gamepad(GamepadButtonType::DPadRight),
+        ],
+        _ => return false,
+    };
+    bindings == stale
+}
+
+impl From<StoredInputMap> for InputMap {
+    fn from(stored: StoredInputMap) -> Self {
+        let mut input_map = InputMap::default();
+        for (action, bindings) in stored.bindings {
+            if stored.version == 0 && is_stale_default(action, &bindings) {
+                continue;
+            }
+            input_map.bindings.insert(action, bindings);
+        }
+        input_map
+    }
+}
+
+impl From<InputMap> for StoredInputMap {
+    fn from(input_map: InputMap) -> Self {
+        let defaults = InputMap::default();
+        let bindings = input_map
+            .bindings
+            .into_iter()
+            .filter(|(action, bindings)| defaults.bindings(*action) != bindings.as_slice())
+            .collect();
+        StoredInputMap {
+            version: STORAGE_VERSION,
+            bindings,
+        }
+    }
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        let bindings = BTreeMap::from([
+            (
+                InputAction::MoveUp,
+                vec![InputBinding::Key(KeyCode::K), InputBinding::Key(KeyCode::W)],
+            ),
+            (
+                InputAction::MoveDown,
+                vec![InputBinding::Key(KeyCode::J), InputBinding::Key(KeyCode::S)],
+            ),
+            (InputAction::MoveLeft, vec![InputBinding::Key(KeyCode::A)]),
+            (
+                InputAction::MoveRight,
+                vec![InputBinding::Key(KeyCode::F), InputBinding::Key(KeyCode::D)],
+            ),
+            (
+                InputAction::Attack,
+                vec![
+                    InputBinding::Mouse(MouseButton::Left),
+                    InputBinding::Gamepad(GamepadButtonType::RightTrigger2),
+                    InputBinding::Gamepad(GamepadButtonType::RightTrigger),
+                ],
+            ),
+            (
+                InputAction::Dash,
+                vec![
+                    InputBinding::Key(KeyCode::Space),
+                    InputBinding::Gamepad(GamepadButtonType::LeftTrigger2),
+                    InputBinding::Gamepad(GamepadButtonType::South),
+                ],
+            ),
+            (
+                InputAction::Hook,
+                vec![
+                    InputBinding::Key(KeyCode::ShiftLeft),
+                    InputBinding::Gamepad(GamepadButtonType::LeftTrigger),
+                ],
+            ),
+            (
+                InputAction::Escape,
+                vec![
+                    InputBinding::Key(KeyCode::Escape),
+                    InputBinding::Gamepad(GamepadButtonType::Start),
+                ],
+            ),
+            (
+                InputAction::ToggleFullscreen,
+                vec![
+                    InputBinding::Key(KeyCode::B),
+                    InputBinding::Gamepad(GamepadButtonType::RightThumb),
+                ],
+            ),
+            (
+                InputAction::Restart,
+                vec![
+                    InputBinding::Key(KeyCode::R),
+                    InputBinding::Gamepad(GamepadButtonType::Select),
+                ],
+            ),
+            (
+                InputAction::SwitchTab,
+                vec![
+                    InputBinding::Key(KeyCode::Tab),
+                    InputBinding::Gamepad(GamepadButtonType::North),
+                ],
+            ),
+            (
+                InputAction::PreviousPage,
+                vec![
+                    InputBinding::Key(KeyCode::PageUp),
+                    InputBinding::Key(KeyCode::Up),
+                    InputBinding::Gamepad(GamepadButtonType::DPadLeft),
+                ],
+            ),
+            (
+                InputAction::NextPage,
+                vec![
+                    InputBinding::Key(KeyCode::PageDown),
+                    InputBinding::Key(KeyCode::Down),
+                    InputBinding::Gamepad(GamepadButtonType::DPadRight),
+                ],
+            ),
+            (
+                InputAction::SwitchFilter,
+                vec![
+                    InputBinding::Key(KeyCode::F),
+                    InputBinding::Gamepad(GamepadButtonType::West),
+                ],
+            ),
+            (
+                InputAction::JumpToRank,
+                vec![
+                    InputBinding::Key(KeyCode::Home),
+                    InputBinding::Gamepad(GamepadButtonType::East),
+                ],
+            ),
+            (
+                InputAction::MenuUp,
+                vec![
+                    InputBinding::Key(KeyCode::Up),
+                    InputBinding::Key(KeyCode::W),
+                    InputBinding::Gamepad(GamepadButtonType::DPadUp),
+                ],
+            ),
+            (
+                InputAction::MenuDown,
+                vec![
+                    InputBinding::Key(KeyCode::Down),
+                    InputBinding::Key(KeyCode::S),
+                    InputBinding::Gamepad(GamepadButtonType::DPadDown),
+                ],
+            ),
+            (
+                InputAction::MenuLeft,
+                vec![
+                    InputBinding::Key(KeyCode::Left),
+                    InputBinding::Key(KeyCode::A),
+                    InputBinding::Gamepad(GamepadButtonType::DPadLeft),
+                ],
+            ),
+            (
+                InputAction::MenuRight,
+                vec![
+                    InputBinding::Key(KeyCode::Right),
+                    InputBinding::Key(KeyCode::D),
+                    InputBinding::Gamepad(GamepadButtonType::DPadRight),
+                ],
+            ),
+            (
+                InputAction::Confirm,
+                vec![
+                    InputBinding::Key(KeyCode::Return),
+                    InputBinding::Key(KeyCode::Space),
+                    InputBinding::Gamepad(GamepadButtonType::South),
+                ],
+            ),
+            (
+                InputAction::ToggleDebug,
+                vec![InputBinding::Key(KeyCode::F1)],
+            ),
+        ]);
+        Self { bindings }
+    }
+}
+
+impl InputMap {
+    /// Load the input map from before it was part of the `Settings`.
+    pub fn load() -> Self {
+        storage::load::<InputMap>(STORAGE_KEY).unwrap_or_default()
+    }
+
+    /// Replace the binding of `action` on the same kind of device (keyboard and mouse or gamepad),
+    /// the bindings on the other kind are kept.
+    pub fn rebind(&mut self, action: InputAction, binding: InputBinding) {
+        let bindings = self.bindings.entry(action).or_default();
+        match bindings
+            .iter()
+            .position(|b| b.is_gamepad() == binding.is_gamepad())
+        {
+            Some(i) => bindings[i] = binding,
+            None => bindings.push(binding),
+        }
+    }
+
+    pub fn bindings(&self, action: InputAction) -> &[InputBinding] {
+        self.bindings
+            .get(&action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// Query `InputAction`s instead of raw keys, buttons and gamepad buttons.
+#[derive(SystemParam)]
+pub struct ActionInput<'w> {
+    input_map: Res<'w, InputMap>,
+    keys: Res<'w, Input<KeyCode>>,
+    mouse_buttons: Res<'w, Input<MouseButton>>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_buttons: Res<'w, Input<GamepadButton>>,
+}
+
+impl ActionInput<'_> {
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.input_map
+            .bindings(action)
+            .iter()
+            .any(|binding| self.binding_pressed(binding, false))
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.input_map
+            .bindings(action)
+            .iter()
+            .any(|binding| self.binding_pressed(binding, true))
+    }
+
+    fn binding_pressed(&self, binding: &InputBinding, just: bool) -> bool {
+        match *binding {
+            InputBinding::Key(key) => {
+                if just {
+                    self.keys.just_pressed(key)
+                } else {
+                    self.keys.pressed(key)
+                }
+            }
+            InputBinding::Mouse(button) => {
+                if just {
+                    self.mouse_buttons.just_pressed(button)
+                } else {
+                    self.mouse_buttons.pressed(button)
+                }
+            }
+            InputBinding::Gamepad(button_type) => self.gamepads.iter().any(|gamepad| {
+                let button = GamepadButton::new(gamepad, button_type);
+                if just {
+                    self.gamepad_buttons.just_pressed(button)
+                } else {
+                    self.gamepad_buttons.pressed(button)
+                }
+            }),
+        }
+    }
+}
+
+pub struct InputMapPlugin;
+
+impl Plugin for InputMapPlugin {
+    fn build(&self, app: &mut App) {
+        // The stored bindings are applied by the settings.
+        app.init_resource::<InputMap>();
+    }
+}
@@ -1,12 +1,15 @@
+pub mod combo;
 pub mod dash;
 pub mod hook;
 pub mod input;
+pub mod input_map;
 pub mod kill_counter;
 pub mod reflection_projectile;
 pub mod score;
 pub mod spawn;
 pub mod speed_timer;
 pub mod state;
+pub mod stats;
 pub mod strike;
 
 mod collision;
@@ -42,6 +45,8 @@ impl Plugin for PlayerPlugin {
             reflection_projectile::PlayerReflectionProjectilePlugin,
             kill_counter::PlayerKillCounterPlugin,
             score::PlayerScorePlugin,
+            combo::PlayerComboPlugin,
+            stats::PlayerStatsPlugin,
         ));
     }
 }
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
-use crate::GameState;
+use crate::GameplaySet;
 
 use super::input::PlayerInput;
 use super::{
@@ -64,8 +64,8 @@ pub struct PlayerMovementPlugin;
 impl Plugin for PlayerMovementPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
-            (player_movement, slide_player).run_if(in_state(GameState::Gaming)),
+            FixedUpdate,
+            (player_movement, slide_player).in_set(GameplaySet),
         );
     }
 }
@@ -2,20 +2,33 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use bevy_trickfilm::prelude::*;
 
-use crate::{utils::quat_from_vec2, world::camera::YSort, GameAssets, GameState};
+use crate::{
+    enemy::{SpatialGrid, SpatialGridSet},
+    utils::{quat_from_vec2, turn_towards},
+    world::{camera::YSort, determinism::AddTickEvent},
+    GameAssets, GameState, GameplaySet,
+};
 
 const PROJECTILE_SPEED: f32 = 800.0;
+/// Homing reflections only look for enemies this close.
+const HOMING_RANGE: f32 = 600.0;
 
 #[derive(Component, Default)]
 pub struct ReflectionProjectile {
     disabled: bool,
     enemy_counter: u32,
+    speed_multiplier: f32,
+    turn_rate: f32,
 }
 
+/// The properties of the reflected projectile carry over to the reflection.
 #[derive(Event)]
 pub struct SpawnReflectionProjectile {
     pub pos: Vec2,
     pub dir: Vec2,
+    pub speed_multiplier: f32,
+    /// How fast the reflection turns towards the closest enemy in radians per second.
+    pub turn_rate: f32,
 }
 
 impl ReflectionProjectile {
@@ -54,7 +67,11 @@ fn spawn_reflection_projectiles(
 
         commands
             .spawn((
-                ReflectionProjectile::default(),
+                ReflectionProjectile {
+                    speed_multiplier: ev.speed_multiplier,
+                    turn_rate: ev.turn_rate,
+                    ..default()
+                },
                 RigidBody::Dynamic,
                 animator,
                 YSort(1.0),
@@ -87,11 +104,24 @@ fn disable_reflection_projectiles(mut q_projectiles: Query<&mut ReflectionProjec
 
 fn move_projectiles(
     time: Res<Time>,
-    mut q_projectiles: Query<&mut Transform, With<ReflectionProjectile>>,
+    grid: Res<SpatialGrid>,
+    mut q_projectiles: Query<(&mut Transform, &ReflectionProjectile)>,
 ) {
-    for mut transform in &mut q_projectiles {
+    for (mut transform, projectile) in &mut q_projectiles {
+        if projectile.turn_rate > 0.0 {
+            let pos = transform.translation.truncate();
+            if let Some(target) = grid.nearest(pos, HOMING_RANGE, |_| true) {
+                transform.rotation = turn_towards(
+                    transform.rotation,
+                    target.pos - pos,
+                    projectile.turn_rate * time.delta_seconds(),
+                );
+            }
+        }
+
         let dir = transform.local_x();
-        transform.translation += dir * PROJECTILE_SPEED * time.delta_seconds();
+        transform.translation +=
+            dir * PROJECTILE_SPEED * projectile.speed_multiplier * time.delta_seconds();
     }
 }
 
@@ -100,14 +130,21 @@ pub struct PlayerReflectionProjectilePlugin;
 impl Plugin for PlayerReflectionProjectilePlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
-            (spawn_reflection_projectiles,).run_if(in_state(GameState::Gaming)),
+            FixedUpdate,
+            (
+                spawn_reflection_projectiles,
+                move_projectiles.after(SpatialGridSet),
+            )
+                .in_set(GameplaySet),
         )
-        .add_event::<SpawnReflectionProjectile>()
+        .add_tick_event::<SpawnReflectionProjectile>()
         .add_systems(
             OnEnter(GameState::Restart),
             (disable_reflection_projectiles,),
         )
-        .add_systems(Update, (despawn_reflection_projectiles, move_projectiles));
+        .add_systems(
+            FixedUpdate,
+            (despawn_reflection_projectiles,).after(GameplaySet),
+        );
     }
 }
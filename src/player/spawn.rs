@@ -4,7 +4,7 @@ use bevy_trickfilm::prelude::*;
 
 use crate::utils::{FixedRotation, COLLISION_GROUPS_NONE};
 use crate::world::camera::YSort;
-use crate::{GameAssets, GameState};
+use crate::{GameAssets, GameState, GameplaySet};
 
 use super::{Player, PLAYER_HITBOX_OFFSET, PLAYER_SPAWN_POS};
 
@@ -104,10 +104,9 @@ pub struct PlayerSpawnPlugin;
 impl Plugin for PlayerSpawnPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::Gaming), spawn_player)
-            .add_systems(
-                Update,
-                (trigger_game_over,).run_if(in_state(GameState::Gaming)),
-            )
-            .add_systems(OnEnter(GameState::GameOver), despawn_player);
+            .add_systems(FixedUpdate, (trigger_game_over,).in_set(GameplaySet))
+            .add_systems(OnEnter(GameState::GameOver), despawn_player)
+            // The run can also be restarted from the pause menu while the player is alive.
+            .add_systems(OnEnter(GameState::Restart), despawn_player);
     }
 }
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::GameState;
+use crate::GameplaySet;
 
 #[derive(Resource, Default)]
 pub struct SpeedTimer {
@@ -16,6 +16,6 @@ pub struct SpeedTimerPlugin;
 impl Plugin for SpeedTimerPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<SpeedTimer>()
-            .add_systems(Update, (tick,).run_if(in_state(GameState::Gaming)));
+            .add_systems(FixedUpdate, (tick,).in_set(GameplaySet));
     }
 }
@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 use bevy_trickfilm::prelude::*;
 
-use crate::{GameAssets, GameState};
+use crate::{
+    world::determinism::{AddTickEvent, TickSet},
+    GameAssets,
+};
 
 use super::{Player, HOOK_TIME};
 
@@ -115,7 +118,7 @@ pub struct PlayerStatePlugin;
 impl Plugin for PlayerStatePlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            PostUpdate,
+            FixedUpdate,
             (
                 player_changed_state,
                 update_animations,
@@ -123,8 +126,8 @@ impl Plugin for PlayerStatePlugin {
                 stop_hooking.after(update_animations),
                 adjust_sprite_flip,
             )
-                .run_if(in_state(GameState::Gaming)),
+                .in_set(TickSet::Late),
         )
-        .add_event::<PlayerChangedState>();
+        .add_tick_event::<PlayerChangedState>();
     }
 }
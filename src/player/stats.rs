@@ -0,0 +1,121 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    enemy::{DespawnEnemy, HitSource},
+    GameplaySet,
+};
+
+use super::{
+    reflection_projectile::ReflectionProjectile,
+    state::{PlayerChangedState, PlayerState},
+    Player,
+};
+
+/// Statistics of the current run, shown on the game over screen and sent with the score.
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+pub struct RunStats {
+    /// Kills by the name of the enemy in the roster.
+    pub kills_by_enemy: BTreeMap<String, u32>,
+    /// Kills by the index of the strike in the strike chain.
+    pub strike_kills: Vec<u32>,
+    pub dash_kills: u32,
+    pub landing_kills: u32,
+    pub reflection_kills: u32,
+    /// Enemies that were run over by a charging enemy.
+    pub charge_kills: u32,
+    /// The most enemies a single reflection projectile killed.
+    pub longest_reflection_chain: u32,
+    pub hooks_landed: u32,
+    pub dashes_used: u32,
+    pub distance_travelled: f32,
+    pub highest_combo: u32,
+    #[serde(skip)]
+    last_player_pos: Option<Vec2>,
+}
+
+impl RunStats {
+    fn register_kill(&mut self, name: &str, source: Option<HitSource>, strike_index: usize) {
+        *self.kills_by_enemy.entry(name.to_string()).or_default() += 1;
+
+        match source {
+            Some(HitSource::Strike) => {
+                if self.strike_kills.len() <= strike_index {
+                    self.strike_kills.resize(strike_index + 1, 0);
+                }
+                self.strike_kills[strike_index] += 1;
+            }
+            Some(HitSource::Dash) => self.dash_kills += 1,
+            Some(HitSource::DashLanding) => self.landing_kills += 1,
+            Some(HitSource::Reflection) => self.reflection_kills += 1,
+            Some(HitSource::Charge) => self.charge_kills += 1,
+            Some(HitSource::Hook) | None => {}
+        }
+    }
+}
+
+fn track_kills(mut run_stats: ResMut<RunStats>, mut ev_despawn_enemy: EventReader<DespawnEnemy>) {
+    for ev in ev_despawn_enemy.read() {
+        run_stats.register_kill(
+            &ev.enemy.name,
+            ev.enemy.killed_by,
+            ev.enemy.strike_index.unwrap_or_default(),
+        );
+        run_stats.highest_combo = run_stats.highest_combo.max(ev.combo);
+    }
+}
+
+fn track_reflection_chains(
+    mut run_stats: ResMut<RunStats>,
+    q_projectiles: Query<&ReflectionProjectile>,
+) {
+    for projectile in &q_projectiles {
+        run_stats.longest_reflection_chain = run_stats
+            .longest_reflection_chain
+            .max(projectile.enemy_counter());
+    }
+}
+
+fn track_player_states(
+    mut run_stats: ResMut<RunStats>,
+    mut ev_player_changed_state: EventReader<PlayerChangedState>,
+) {
+    for ev in ev_player_changed_state.read() {
+        match ev.new_state {
+            PlayerState::Dashing => run_stats.dashes_used += 1,
+            PlayerState::Sliding => run_stats.hooks_landed += 1,
+            _ => {}
+        }
+    }
+}
+
+fn track_distance(mut run_stats: ResMut<RunStats>, q_player: Query<&Transform, With<Player>>) {
+    let pos = match q_player.get_single() {
+        Ok(r) => r.translation.truncate(),
+        Err(_) => return,
+    };
+
+    if let Some(last_pos) = run_stats.last_player_pos {
+        run_stats.distance_travelled += last_pos.distance(pos);
+    }
+    run_stats.last_player_pos = Some(pos);
+}
+
+pub struct PlayerStatsPlugin;
+
+impl Plugin for PlayerStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>().add_systems(
+            FixedUpdate,
+            (
+                track_kills,
+                track_reflection_chains,
+                track_player_states,
+                track_distance,
+            )
+                .in_set(GameplaySet),
+        );
+    }
+}
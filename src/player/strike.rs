@@ -7,8 +7,8 @@ use bevy_trickfilm::prelude::*;
 use crate::{
     audio::PlaySound,
     utils::{quat_from_vec2, FixedRotation},
-    world::camera::YSort,
-    GameAssets, GameState,
+    world::{camera::YSort, determinism::AddTickEvent},
+    GameAssets, GameplaySet,
 };
 
 use super::{
@@ -31,7 +31,10 @@ struct StrikeCooldown {
 }
 
 #[derive(Component)]
-pub struct Strike;
+pub struct Strike {
+    /// The index of the strike in the strike chain.
+    pub index: usize,
+}
 #[derive(Component)]
 pub struct StrikeCollider {
     timer: Timer,
@@ -91,7 +94,9 @@ fn spawn_strikes(
                     offset: OFFSET,
                     rot: ev.rot,
                 },
-                Strike,
+                Strike {
+                    index: ev.strike_index,
+                },
                 YSort(1.0),
                 animator,
                 SpriteSheetBundle {
@@ -158,7 +163,9 @@ fn trigger_strike(
         return;
     }
 
-    let rot = quat_from_vec2(mouse_coords.0 - player_transform.translation.truncate());
+    let rot = quat_from_vec2(
+        player_input.aim_direction(&mouse_coords, player_transform.translation.truncate()),
+    );
 
     if !player_input.attack {
         return;
@@ -215,7 +222,7 @@ pub struct PlayerStrikePlugin;
 impl Plugin for PlayerStrikePlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-            Update,
+            FixedUpdate,
             (
                 spawn_strikes,
                 despawn_strikes,
@@ -225,9 +232,9 @@ impl Plugin for PlayerStrikePlugin {
                 tick_strike_cooldown,
                 tick_strike_collider_timers,
             )
-                .run_if(in_state(GameState::Gaming)),
+                .in_set(GameplaySet),
         )
         .init_resource::<StrikeCooldown>()
-        .add_event::<SpawnStrike>();
+        .add_tick_event::<SpawnStrike>();
     }
 }
@@ -0,0 +1,51 @@
+//! The JSON format spoken between the game and the leaderboard server.
+//!
+//! `POST {base_url}/leaderboard` takes a `ScoreSubmission` and answers with a `SubmissionResponse`.
+//! `GET {base_url}/leaderboard?period=<period>&offset=<offset>&limit=<limit>&id=<id>&daily=<date>`
+//! answers with a `LeaderboardResponse`, the optional `id` is the entry of the player.
+//! Daily runs have their own table for every date, they are only listed with `daily=<date>`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::player::stats::RunStats;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ScoreSubmission {
+    pub name: String,
+    pub score: u32,
+    pub kills: u32,
+    pub time: f32,
+    pub seed: u32,
+    /// The date of the daily run, `None` for endless runs.
+    pub daily: Option<String>,
+    pub stats: RunStats,
+    /// The base64 encoded replay so that the server can verify the run.
+    pub replay: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SubmissionResponse {
+    /// The id of the new entry.
+    pub id: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RemoteEntry {
+    pub id: u64,
+    /// The rank in the requested period, starting at `0`.
+    pub rank: usize,
+    pub name: String,
+    pub score: u32,
+    pub kills: u32,
+    pub time: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LeaderboardResponse {
+    pub entries: Vec<RemoteEntry>,
+    /// The number of entries in the requested period.
+    pub total: usize,
+    /// The entry with the requested `id`, if it is in the requested period.
+    #[serde(default)]
+    pub own: Option<RemoteEntry>,
+}
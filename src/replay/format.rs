@@ -0,0 +1,242 @@
+use std::fmt;
+
+use bevy::prelude::*;
+
+use crate::player::input::{MouseWorldCoords, PlayerInput};
+
+const MAGIC: &[u8; 4] = b"IKRP";
+/// Bump this whenever the layout of the replay file changes.
+const FORMAT_VERSION: u16 = 1;
+/// Replays of other game versions would desync, so we reject them.
+pub const GAME_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const ATTACK: u8 = 1 << 0;
+const DASH: u8 = 1 << 1;
+const HOOK: u8 = 1 << 2;
+const AIM_STICK: u8 = 1 << 3;
+/// The repetition count, the flags, the move direction and the mouse coordinates.
+const MIN_RUN_SIZE: usize = 2 + 1 + 8 + 8;
+
+/// The gameplay relevant input of a single tick.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ReplayTick {
+    pub move_direction: Vec2,
+    pub aim_stick: Option<Vec2>,
+    pub attack: bool,
+    pub dash: bool,
+    pub hook: bool,
+    pub mouse_coords: Vec2,
+}
+
+#[derive(Debug, Clone)]
+pub struct Replay {
+    pub game_version: String,
+    pub seed: u32,
+    pub ticks: Vec<ReplayTick>,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    InvalidMagic,
+    UnsupportedFormat(u16),
+    GameVersionMismatch(String),
+    Truncated,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::InvalidMagic => write!(f, "not a replay file"),
+            ReplayError::UnsupportedFormat(version) => write!(
+                f,
+                "unsupported replay format {}, expected {}",
+                version, FORMAT_VERSION
+            ),
+            ReplayError::GameVersionMismatch(version) => write!(
+                f,
+                "replay was recorded with version {}, this is version {}",
+                version, GAME_VERSION
+            ),
+            ReplayError::Truncated => write!(f, "replay file is truncated"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl ReplayTick {
+    pub fn capture(player_input: &PlayerInput, mouse_coords: &MouseWorldCoords) -> Self {
+        Self {
+            move_direction: player_input.move_direction,
+            aim_stick: player_input.aim_stick,
+            attack: player_input.attack,
+            dash: player_input.dash,
+            hook: player_input.hook,
+            mouse_coords: mouse_coords.0,
+        }
+    }
+
+    pub fn apply(&self, player_input: &mut PlayerInput, mouse_coords: &mut MouseWorldCoords) {
+        player_input.move_direction = self.move_direction;
+        player_input.aim_stick = self.aim_stick;
+        player_input.attack = self.attack;
+        player_input.dash = self.dash;
+        player_input.hook = self.hook;
+        mouse_coords.0 = self.mouse_coords;
+    }
+
+    fn write(&self, bytes: &mut Vec<u8>) {
+        let mut flags = 0;
+        if self.attack {
+            flags |= ATTACK;
+        }
+        if self.dash {
+            flags |= DASH;
+        }
+        if self.hook {
+            flags |= HOOK;
+        }
+        if self.aim_stick.is_some() {
+            flags |= AIM_STICK;
+        }
+
+        bytes.push(flags);
+        write_vec2(bytes, self.move_direction);
+        write_vec2(bytes, self.mouse_coords);
+        if let Some(aim_stick) = self.aim_stick {
+            write_vec2(bytes, aim_stick);
+        }
+    }
+
+    fn read(reader: &mut Reader) -> Result<Self, ReplayError> {
+        let flags = reader.u8()?;
+        let move_direction = reader.vec2()?;
+        let mouse_coords = reader.vec2()?;
+        let aim_stick = if flags & AIM_STICK != 0 {
+            Some(reader.vec2()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            move_direction,
+            aim_stick,
+            attack: flags & ATTACK != 0,
+            dash: flags & DASH != 0,
+            hook: flags & HOOK != 0,
+            mouse_coords,
+        })
+    }
+}
+
+impl Replay {
+    pub fn new(seed: u32, ticks: Vec<ReplayTick>) -> Self {
+        Self {
+            game_version: GAME_VERSION.to_string(),
+            seed,
+            ticks,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.push(self.game_version.len() as u8);
+        bytes.extend_from_slice(self.game_version.as_bytes());
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&(self.ticks.len() as u32).to_le_bytes());
+
+        // Consecutive ticks are often identical (standing still, holding a direction),
+        // so we store the number of repetitions followed by the tick.
+        let mut i = 0;
+        while i < self.ticks.len() {
+            let tick = self.ticks[i];
+            let repeat = self.ticks[i..]
+                .iter()
+                .take(u16::MAX as usize)
+                .take_while(|other| **other == tick)
+                .count();
+
+            bytes.extend_from_slice(&(repeat as u16).to_le_bytes());
+            tick.write(&mut bytes);
+            i += repeat;
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReplayError> {
+        let mut reader = Reader { bytes };
+
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(ReplayError::InvalidMagic);
+        }
+        let format_version = reader.u16()?;
+        if format_version != FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedFormat(format_version));
+        }
+        let version_len = reader.u8()? as usize;
+        let game_version = String::from_utf8_lossy(reader.take(version_len)?).to_string();
+        if game_version != GAME_VERSION {
+            return Err(ReplayError::GameVersionMismatch(game_version));
+        }
+
+        let seed = reader.u32()?;
+        let tick_count = reader.u32()? as usize;
+
+        // The tick count comes from the file, only reserve what the remaining bytes can hold.
+        let max_ticks = reader.bytes.len() / MIN_RUN_SIZE * u16::MAX as usize;
+        let mut ticks = Vec::with_capacity(tick_count.min(max_ticks));
+        while ticks.len() < tick_count {
+            let repeat = reader.u16()? as usize;
+            let tick = ReplayTick::read(&mut reader)?;
+            ticks.extend(std::iter::repeat(tick).take(repeat.min(tick_count - ticks.len())));
+        }
+
+        Ok(Self {
+            game_version,
+            seed,
+            ticks,
+        })
+    }
+}
+
+fn write_vec2(bytes: &mut Vec<u8>, v: Vec2) {
+    bytes.extend_from_slice(&v.x.to_le_bytes());
+    bytes.extend_from_slice(&v.y.to_le_bytes());
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ReplayError> {
+        if self.bytes.len() < n {
+            return Err(ReplayError::Truncated);
+        }
+        let (head, tail) = self.bytes.split_at(n);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, ReplayError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, ReplayError> {
+        let b = self.take(2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, ReplayError> {
+        let b = self.take(4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn vec2(&mut self) -> Result<Vec2, ReplayError> {
+        let x = f32::from_bits(self.u32()?);
+        let y = f32::from_bits(self.u32()?);
+        Ok(Vec2::new(x, y))
+    }
+}
@@ -0,0 +1,19 @@
+mod format;
+mod playback;
+mod record;
+mod verify;
+
+pub use format::{Replay, ReplayError, ReplayTick};
+pub use playback::ReplayPlayback;
+pub use record::{DisableReplayRecording, LastReplay};
+pub use verify::{verify_replay, VerifyError};
+
+use bevy::prelude::*;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((record::ReplayRecordPlugin, playback::ReplayPlaybackPlugin));
+    }
+}
@@ -0,0 +1,119 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+
+use crate::player::input::{MouseWorldCoords, PlayerInput};
+use crate::world::determinism::TickSet;
+use crate::GameState;
+
+use super::format::Replay;
+
+/// Feeds the inputs of a recorded run into `PlayerInput` instead of the live devices.
+#[derive(Resource)]
+pub struct ReplayPlayback {
+    replay: Replay,
+    cursor: usize,
+    desynced: bool,
+}
+
+impl ReplayPlayback {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            cursor: 0,
+            desynced: false,
+        }
+    }
+
+    pub fn load(path: &Path) -> Option<Self> {
+        let bytes = match std::fs::read(path) {
+            Ok(r) => r,
+            Err(err) => {
+                error!("failed to read replay {}, {}", path.display(), err);
+                return None;
+            }
+        };
+        match Replay::from_bytes(&bytes) {
+            Ok(r) => {
+                info!(
+                    "playing replay of version {} with seed {}",
+                    r.game_version, r.seed
+                );
+                Some(Self::new(r))
+            }
+            Err(err) => {
+                error!("failed to load replay {}, {}", path.display(), err);
+                None
+            }
+        }
+    }
+
+    /// Load the replay from the `--replay <path>` command line argument.
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = std::env::args().collect();
+        let path = args
+            .iter()
+            .position(|arg| arg == "--replay")
+            .and_then(|i| args.get(i + 1))?;
+        Self::load(Path::new(path))
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.replay.seed
+    }
+
+    pub fn finished(&self) -> bool {
+        self.cursor >= self.replay.ticks.len()
+    }
+
+    /// The run was still going when the replay ended.
+    pub fn desynced(&self) -> bool {
+        self.desynced
+    }
+}
+
+fn apply_replay_tick(
+    playback: Res<ReplayPlayback>,
+    mut player_input: ResMut<PlayerInput>,
+    mut mouse_coords: ResMut<MouseWorldCoords>,
+) {
+    let tick = playback
+        .replay
+        .ticks
+        .get(playback.cursor)
+        .copied()
+        .unwrap_or_default();
+    tick.apply(&mut player_input, &mut mouse_coords);
+}
+
+fn advance_replay(
+    mut playback: ResMut<ReplayPlayback>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if playback.finished() {
+        warn!("replay ended before the run did, it's out of sync");
+        playback.desynced = true;
+        next_state.set(GameState::GameOver);
+        return;
+    }
+    playback.cursor += 1;
+}
+
+fn rewind_replay(mut playback: ResMut<ReplayPlayback>) {
+    playback.cursor = 0;
+    playback.desynced = false;
+}
+
+pub struct ReplayPlaybackPlugin;
+
+impl Plugin for ReplayPlaybackPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<ReplayPlayback>() {
+            return;
+        }
+
+        app.add_systems(FixedUpdate, apply_replay_tick.in_set(TickSet::Input))
+            .add_systems(FixedUpdate, advance_replay.in_set(TickSet::Late))
+            .add_systems(OnEnter(GameState::Restart), rewind_replay);
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::player::input::{MouseWorldCoords, PlayerInput};
+use crate::world::determinism::TickSet;
+use crate::world::seed::Seed;
+use crate::GameState;
+
+use super::format::{Replay, ReplayTick};
+use super::playback::ReplayPlayback;
+
+/// Don't record any replays, for instance when simulating thousands of runs.
+#[derive(Resource)]
+pub struct DisableReplayRecording;
+
+/// The replay of the run that just ended.
+#[derive(Resource, Deref)]
+pub struct LastReplay(pub Replay);
+
+#[derive(Resource, Default)]
+struct ReplayRecorder {
+    ticks: Vec<ReplayTick>,
+}
+
+fn reset_recorder(mut recorder: ResMut<ReplayRecorder>) {
+    recorder.ticks.clear();
+}
+
+fn record_tick(
+    mut recorder: ResMut<ReplayRecorder>,
+    player_input: Res<PlayerInput>,
+    mouse_coords: Res<MouseWorldCoords>,
+) {
+    recorder
+        .ticks
+        .push(ReplayTick::capture(&player_input, &mouse_coords));
+}
+
+fn finish_recording(mut commands: Commands, seed: Res<Seed>, mut recorder: ResMut<ReplayRecorder>) {
+    let replay = Replay::new(seed.0, std::mem::take(&mut recorder.ticks));
+    // There is no file system on the web, the replay is only kept in memory there.
+    #[cfg(not(target_arch = "wasm32"))]
+    write_replay(&replay);
+    commands.insert_resource(LastReplay(replay));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_replay(replay: &Replay) {
+    let dir = match dirs::data_dir() {
+        Some(r) => r.join("insta-kill").join("replays"),
+        None => {
+            warn!("there is no data directory on this platform, can't save replay");
+            return;
+        }
+    };
+    if let Err(err) = std::fs::create_dir_all(&dir) {
+        error!("failed to create {}, {}", dir.display(), err);
+        return;
+    }
+
+    let path = dir.join(format!(
+        "{}-{}.ikr",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S"),
+        replay.seed
+    ));
+    match std::fs::write(&path, replay.to_bytes()) {
+        Ok(()) => info!("saved replay to {}", path.display()),
+        Err(err) => error!("failed to write {}, {}", path.display(), err),
+    }
+}
+
+/// Records the input of every tick of every run.
+pub struct ReplayRecordPlugin;
+
+impl Plugin for ReplayRecordPlugin {
+    fn build(&self, app: &mut App) {
+        if app.world.contains_resource::<ReplayPlayback>()
+            || app.world.contains_resource::<DisableReplayRecording>()
+        {
+            return;
+        }
+
+        app.init_resource::<ReplayRecorder>()
+            .add_systems(OnEnter(GameState::Gaming), reset_recorder)
+            .add_systems(FixedUpdate, record_tick.in_set(TickSet::Input))
+            .add_systems(OnEnter(GameState::GameOver), finish_recording);
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt;
+
+use bevy::prelude::*;
+
+use crate::headless::{game_state, headless_plugins, prepare_app, RunSummary};
+use crate::utils::storage::DisableStorage;
+use crate::world::{determinism::FrameStepping, seed::SeedOverride};
+use crate::{GamePlugin, GameState};
+
+use super::format::Replay;
+use super::playback::ReplayPlayback;
+
+#[derive(Debug)]
+pub enum VerifyError {
+    AssetLoading,
+    Desync,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VerifyError::AssetLoading => write!(f, "assets didn't finish loading"),
+            VerifyError::Desync => write!(f, "the run didn't end together with the replay"),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Re-simulate the run of the given replay headlessly and return its outcome.
+/// This takes a while because all game assets have to be loaded first.
+pub fn verify_replay(replay: Replay) -> Result<RunSummary, VerifyError> {
+    let tick_count = replay.ticks.len();
+
+    let mut app = App::new();
+    app.insert_resource(SeedOverride(Some(replay.seed)))
+        .insert_resource(FrameStepping { active: true })
+        .insert_resource(ReplayPlayback::new(replay))
+        .insert_resource(DisableStorage)
+        .add_plugins((headless_plugins(), GamePlugin));
+
+    if !prepare_app(&mut app) {
+        return Err(VerifyError::AssetLoading);
+    }
+
+    // The game over state is only entered on the update after the last tick.
+    for _ in 0..=tick_count {
+        if game_state(&app) == GameState::GameOver {
+            break;
+        }
+        app.update();
+    }
+
+    let playback = app.world.resource::<ReplayPlayback>();
+    if game_state(&app) != GameState::GameOver || !playback.finished() || playback.desynced() {
+        return Err(VerifyError::Desync);
+    }
+
+    Ok(RunSummary::from_world(&app.world))
+}
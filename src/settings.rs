@@ -0,0 +1,196 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::window::PrimaryWindow;
+use bevy::window::WindowMode;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    audio::GameAudio,
+    player::input_map::{self, InputMap},
+    utils::storage::{self, DisableStorage},
+    world::CameraShake,
+};
+
+const STORAGE_KEY: &str = "settings";
+/// How long the settings have to stay unchanged before they are saved,
+/// scrolling the volume changes them every frame.
+const SAVE_DELAY: f32 = 1.0;
+
+/// Everything the player can configure, it's saved shortly after it changes and on exit.
+/// Changes are applied to the resources the game reads (`GameAudio`, `InputMap`, ...).
+#[derive(Resource, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub main_volume: f64,
+    pub music_volume: f64,
+    pub sound_volume: f64,
+    pub fullscreen: bool,
+    /// Scales the camera shake, `0.0` turns it off.
+    pub screen_shake: f32,
+    pub vignette: bool,
+    /// The name that was last entered for the leaderboard.
+    pub username: String,
+    pub input_map: InputMap,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            main_volume: 0.5,
+            music_volume: 1.0,
+            sound_volume: 1.0,
+            fullscreen: false,
+            screen_shake: 1.0,
+            vignette: true,
+            username: String::new(),
+            input_map: InputMap::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        if let Some(settings) = storage::load::<Settings>(STORAGE_KEY) {
+            return settings;
+        }
+
+        // The input map used to be stored on its own.
+        let settings = Settings {
+            input_map: InputMap::load(),
+            ..default()
+        };
+        settings.save();
+        storage::remove(input_map::STORAGE_KEY);
+        settings
+    }
+
+    pub fn save(&self) {
+        storage::save(STORAGE_KEY, self);
+    }
+
+    /// The browser decides about fullscreen on wasm.
+    pub fn window_mode(&self) -> WindowMode {
+        if self.fullscreen && cfg!(not(target_arch = "wasm32")) {
+            WindowMode::Fullscreen
+        } else {
+            WindowMode::Windowed
+        }
+    }
+}
+
+/// Counts down to the next save, `None` if the settings are saved.
+#[derive(Resource, Default)]
+struct PendingSave(Option<Timer>);
+
+fn schedule_save(mut pending_save: ResMut<PendingSave>) {
+    pending_save.0 = Some(Timer::from_seconds(SAVE_DELAY, TimerMode::Once));
+}
+
+fn save_settings(
+    time: Res<Time<Real>>,
+    settings: Res<Settings>,
+    mut pending_save: ResMut<PendingSave>,
+) {
+    let timer = match pending_save.0.as_mut() {
+        Some(r) => r,
+        None => return,
+    };
+
+    if timer.tick(time.delta()).finished() {
+        settings.save();
+        pending_save.0 = None;
+    }
+}
+
+fn save_settings_on_exit(
+    settings: Res<Settings>,
+    mut pending_save: ResMut<PendingSave>,
+    mut ev_app_exit: EventReader<AppExit>,
+) {
+    if ev_app_exit.is_empty() {
+        return;
+    }
+    ev_app_exit.clear();
+
+    if pending_save.0.take().is_some() {
+        settings.save();
+    }
+}
+
+fn apply_audio(settings: Res<Settings>, mut game_audio: ResMut<GameAudio>) {
+    game_audio.main_volume = settings.main_volume;
+    game_audio.music_volume = settings.music_volume;
+    game_audio.sound_volume = settings.sound_volume;
+}
+
+fn apply_camera_shake(settings: Res<Settings>, mut camera_shake: ResMut<CameraShake>) {
+    camera_shake.strength = settings.screen_shake;
+}
+
+fn apply_input_map(settings: Res<Settings>, mut input_map: ResMut<InputMap>) {
+    *input_map = settings.input_map.clone();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn apply_window_mode(
+    settings: Res<Settings>,
+    mut q_window: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let mut window = match q_window.get_single_mut() {
+        Ok(r) => r,
+        Err(_) => return,
+    };
+
+    let mode = settings.window_mode();
+    if window.mode != mode {
+        window.mode = mode;
+    }
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        let storage_disabled = app.world.contains_resource::<DisableStorage>();
+
+        // The settings may already be loaded to create the window with them.
+        if !app.world.contains_resource::<Settings>() {
+            if storage_disabled {
+                app.init_resource::<Settings>();
+            } else {
+                app.insert_resource(Settings::load());
+            }
+        }
+
+        app.add_systems(
+            Update,
+            (
+                apply_audio,
+                apply_camera_shake,
+                apply_input_map,
+                #[cfg(not(target_arch = "wasm32"))]
+                apply_window_mode,
+            )
+                .run_if(resource_changed::<Settings>()),
+        );
+
+        if storage_disabled {
+            return;
+        }
+
+        app.init_resource::<PendingSave>()
+            .add_systems(
+                Update,
+                (
+                    schedule_save
+                        .run_if(resource_changed::<Settings>())
+                        // Inserting the loaded settings counts as a change too.
+                        .run_if(not(resource_added::<Settings>())),
+                    save_settings,
+                )
+                    .chain(),
+            )
+            .add_systems(Last, save_settings_on_exit);
+    }
+}
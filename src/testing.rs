@@ -0,0 +1,346 @@
+//! Support for integration tests of the gameplay systems.
+//!
+//! `TestApp` runs the player and enemy plugins without a window, renderer or audio.
+//! All textures, sounds and the map are stubbed out, only the animation clips and the
+//! enemy roster are loaded from the real assets because a lot of the gameplay depends on them.
+
+use std::time::{Duration, Instant};
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::scene::ScenePlugin;
+use bevy_rapier2d::prelude::*;
+use bevy_trickfilm::prelude::*;
+
+use crate::audio::PlaySound;
+use crate::collision::CollisionRouterPlugin;
+use crate::effect::super_sonic::SpawnSuperSonic;
+use crate::enemy::{
+    spawn_archer_projectile, spawn_enemy, Enemy, EnemyPlugin, EnemyProjectile, EnemyRoster,
+    EnemySpawning,
+};
+use crate::headless::finish_plugins;
+use crate::player::input::{MouseWorldCoords, PlayerInput, PlayerInputSet};
+use crate::player::{
+    combo::Combo, kill_counter::KillCounter, score::PlayerScore, stats::RunStats, Player,
+    PlayerPlugin,
+};
+use crate::replay::ReplayTick;
+use crate::ui::world_text::SpawnWorldText;
+use crate::utils::{quat_from_vec2, UtilsPlugin};
+use crate::world::determinism::{tick_duration, AddTickEvent, DeterminismPlugin, FrameStepping};
+use crate::world::pause::PausePlugin;
+use crate::world::seed::{GameSeedPlugin, SeedOverride};
+use crate::world::world_debug::DebugMode;
+use crate::{GameAssets, GameState};
+
+const ASSET_LOADING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The input of the player, it replaces the live input every tick.
+/// `attack`, `dash` and `hook` are reset after one tick, just like pressing a button.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct TestInput(pub ReplayTick);
+
+fn apply_test_input(
+    mut test_input: ResMut<TestInput>,
+    mut player_input: ResMut<PlayerInput>,
+    mut mouse_coords: ResMut<MouseWorldCoords>,
+) {
+    test_input.apply(&mut player_input, &mut mouse_coords);
+    test_input.attack = false;
+    test_input.dash = false;
+    test_input.hook = false;
+}
+
+fn stub_game_assets(asset_server: &AssetServer) -> GameAssets {
+    let clips = |paths: &[&str]| -> Vec<Handle<AnimationClip2D>> {
+        paths.iter().map(|path| asset_server.load(*path)).collect()
+    };
+
+    GameAssets {
+        player: Handle::default(),
+        player_animations: clips(&[
+            "player/player.trickfilm#idle",
+            "player/player.trickfilm#moving",
+            "player/player.trickfilm#dashing",
+            "player/player.trickfilm#hooking",
+            "player/player.trickfilm#sliding",
+        ]),
+        player_shadow: Handle::default(),
+        player_strike: Handle::default(),
+        player_strike_animations: clips(&["player/player_strike.trickfilm#main"]),
+        player_hook: Handle::default(),
+        player_hook_animations: clips(&["player/player_hook.trickfilm#main"]),
+        player_reflection_projectile: Handle::default(),
+        player_reflection_projectile_animations: clips(&[
+            "player/player_reflection_projectile.trickfilm#main",
+        ]),
+        player_dash_refresh: Handle::default(),
+        enemy_hit: Handle::default(),
+        enemy_hit_animations: clips(&["enemy/enemy_hit.trickfilm#main"]),
+        enemy_roster: asset_server.load("enemy/enemies.ron"),
+        archer_projectile: Handle::default(),
+        archer_projectile_animations: clips(&["enemy/archer/projectile.trickfilm#main"]),
+        level: Handle::default(),
+        super_sonic: Handle::default(),
+        super_sonic_animations: clips(&["effects/super_sonic.trickfilm#main"]),
+        white_pixel: Handle::default(),
+        vignette: Handle::default(),
+        death_counter_icon: Handle::default(),
+        score_icon: Handle::default(),
+        combo_bar_container: Handle::default(),
+        bgm: Handle::default(),
+        strike_sound: Handle::default(),
+        font: Handle::default(),
+    }
+}
+
+fn animation_clips(assets: &GameAssets, roster: &EnemyRoster) -> Vec<Handle<AnimationClip2D>> {
+    let enemy_animations = roster.enemies.iter().flat_map(|enemy| {
+        [
+            &enemy.animations.idle,
+            &enemy.animations.moving,
+            &enemy.animations.attacking,
+            &enemy.animations.stunned,
+        ]
+    });
+
+    [
+        &assets.player_animations,
+        &assets.player_strike_animations,
+        &assets.player_hook_animations,
+        &assets.player_reflection_projectile_animations,
+        &assets.enemy_hit_animations,
+        &assets.archer_projectile_animations,
+        &assets.super_sonic_animations,
+    ]
+    .into_iter()
+    .flatten()
+    .chain(enemy_animations)
+    .cloned()
+    .collect()
+}
+
+fn assets_loaded(world: &World, assets: &GameAssets) -> bool {
+    let roster = match world
+        .resource::<Assets<EnemyRoster>>()
+        .get(&assets.enemy_roster)
+    {
+        Some(r) => r,
+        None => return false,
+    };
+
+    let clips = world.resource::<Assets<AnimationClip2D>>();
+    animation_clips(assets, roster)
+        .iter()
+        .all(|clip| clips.contains(clip))
+}
+
+/// A minimal app with the player in the `Gaming` state, every update is exactly one tick.
+/// Enemies don't spawn on their own, use the `spawn_*` methods instead.
+pub struct TestApp {
+    app: App,
+}
+
+impl Default for TestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestApp {
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            TransformPlugin,
+            HierarchyPlugin,
+            InputPlugin,
+            ScenePlugin,
+            Animation2DPlugin,
+        ))
+        .init_asset::<Image>()
+        .init_asset::<Mesh>()
+        .init_asset::<TextureAtlas>()
+        .insert_resource(FrameStepping { active: true })
+        .add_plugins(DeterminismPlugin)
+        .insert_resource(RapierConfiguration {
+            gravity: Vec2::ZERO,
+            timestep_mode: TimestepMode::Fixed {
+                dt: tick_duration().as_secs_f32(),
+                substeps: 1,
+            },
+            ..default()
+        })
+        .add_plugins(RapierPhysicsPlugin::<NoUserData>::default().in_fixed_schedule())
+        .add_state::<GameState>()
+        .insert_resource(SeedOverride(Some(0)))
+        .insert_resource(EnemySpawning { active: false })
+        .init_resource::<DebugMode>()
+        .init_resource::<TestInput>()
+        .add_tick_event::<PlaySound>()
+        .add_tick_event::<SpawnWorldText>()
+        .add_tick_event::<SpawnSuperSonic>()
+        .add_plugins((
+            GameSeedPlugin,
+            PausePlugin,
+            UtilsPlugin,
+            PlayerPlugin,
+            EnemyPlugin,
+            CollisionRouterPlugin,
+        ))
+        .add_systems(PreUpdate, apply_test_input.after(PlayerInputSet));
+
+        finish_plugins(&mut app);
+
+        let assets = stub_game_assets(app.world.resource::<AssetServer>());
+        let start = Instant::now();
+        while !assets_loaded(&app.world, &assets) {
+            assert!(
+                start.elapsed() < ASSET_LOADING_TIMEOUT,
+                "assets didn't finish loading"
+            );
+            app.update();
+        }
+
+        app.insert_resource(assets);
+        app.world
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Gaming);
+        app.update();
+
+        Self { app }
+    }
+
+    pub fn app(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    /// Advance the game by `n` ticks.
+    pub fn tick(&mut self, n: usize) {
+        for _ in 0..n {
+            self.app.update();
+        }
+    }
+
+    pub fn input(&mut self) -> Mut<TestInput> {
+        self.app.world.resource_mut::<TestInput>()
+    }
+
+    /// Aim in the given direction and strike on the next tick.
+    pub fn strike(&mut self, dir: Vec2) {
+        let mut input = self.input();
+        input.aim_stick = Some(dir.normalize_or_zero());
+        input.attack = true;
+    }
+
+    /// Aim in the given direction and dash on the next tick.
+    pub fn dash(&mut self, dir: Vec2) {
+        let mut input = self.input();
+        input.aim_stick = Some(dir.normalize_or_zero());
+        input.dash = true;
+    }
+
+    /// Aim in the given direction and throw the hook on the next tick.
+    pub fn hook(&mut self, dir: Vec2) {
+        let mut input = self.input();
+        input.aim_stick = Some(dir.normalize_or_zero());
+        input.hook = true;
+    }
+
+    pub fn player(&mut self) -> &Player {
+        self.app.world.query::<&Player>().single(&self.app.world)
+    }
+
+    pub fn player_pos(&mut self) -> Vec2 {
+        self.app
+            .world
+            .query_filtered::<&Transform, With<Player>>()
+            .single(&self.app.world)
+            .translation
+            .truncate()
+    }
+
+    pub fn enemy(&self, entity: Entity) -> Option<&Enemy> {
+        self.app.world.get::<Enemy>(entity)
+    }
+
+    pub fn enemy_pos(&self, entity: Entity) -> Option<Vec2> {
+        self.app
+            .world
+            .get::<Transform>(entity)
+            .map(|transform| transform.translation.truncate())
+    }
+
+    pub fn enemy_mut(&mut self, entity: Entity) -> Option<Mut<Enemy>> {
+        self.app.world.get_mut::<Enemy>(entity)
+    }
+
+    pub fn enemies(&mut self) -> Vec<Entity> {
+        self.app
+            .world
+            .query_filtered::<Entity, With<Enemy>>()
+            .iter(&self.app.world)
+            .collect()
+    }
+
+    /// Spawn the enemy with the given name from the enemy roster.
+    pub fn spawn_enemy(&mut self, name: &str, pos: Vec2) -> Entity {
+        let name = name.to_string();
+        self.app.world.run_system_once(
+            move |mut commands: Commands,
+                  assets: Res<GameAssets>,
+                  rosters: Res<Assets<EnemyRoster>>| {
+                let definition = rosters
+                    .get(&assets.enemy_roster)
+                    .and_then(|roster| roster.get(&name))
+                    .unwrap_or_else(|| panic!("there is no enemy called {}", name));
+                spawn_enemy(&mut commands, definition, pos.extend(0.0))
+            },
+        )
+    }
+
+    /// Spawn an archer projectile at `pos` that flies in the direction `dir`.
+    pub fn spawn_archer_projectile(&mut self, pos: Vec2, dir: Vec2) -> Entity {
+        self.app
+            .world
+            .run_system_once(move |mut commands: Commands, assets: Res<GameAssets>| {
+                spawn_archer_projectile(
+                    &mut commands,
+                    &assets,
+                    pos.extend(0.0),
+                    quat_from_vec2(dir),
+                )
+            })
+    }
+
+    pub fn projectiles(&mut self) -> Vec<Entity> {
+        self.app
+            .world
+            .query_filtered::<Entity, With<EnemyProjectile>>()
+            .iter(&self.app.world)
+            .collect()
+    }
+
+    pub fn kills(&self) -> u32 {
+        self.app.world.resource::<KillCounter>().kills()
+    }
+
+    pub fn score(&self) -> u32 {
+        self.app.world.resource::<PlayerScore>().score()
+    }
+
+    pub fn combo(&self) -> &Combo {
+        self.app.world.resource::<Combo>()
+    }
+
+    pub fn run_stats(&self) -> &RunStats {
+        self.app.world.resource::<RunStats>()
+    }
+
+    pub fn state(&self) -> GameState {
+        self.app.world.resource::<State<GameState>>().get().clone()
+    }
+}
@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+
+use crate::{player::combo::Combo, GameAssets, GameState, GameplaySet};
+
+const WIDTH: f32 = 32.0;
+const HEIGHT: f32 = 180.0;
+/// The inset of the fill from the border of the container sprite.
+const PADDING: f32 = 4.0;
+const FILL_COLOR: Color = Color::rgb(1.0, 0.75, 0.3);
+const FONT_SIZE: f32 = 24.0;
+
+#[derive(Component)]
+struct ComboBar;
+#[derive(Component)]
+struct ComboBarFill;
+#[derive(Component)]
+struct ComboText;
+
+fn spawn_combo_bar(mut commands: Commands, assets: Res<GameAssets>) {
+    let fill = commands
+        .spawn((
+            ComboBarFill,
+            NodeBundle {
+                style: Style {
+                    left: Val::Px(PADDING),
+                    right: Val::Px(PADDING),
+                    bottom: Val::Px(PADDING),
+                    height: Val::Px(0.0),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: FILL_COLOR.into(),
+                ..default()
+            },
+        ))
+        .id();
+
+    // The fill is drawn first so that the border of the container goes over it.
+    let container = commands
+        .spawn(ImageBundle {
+            style: Style {
+                width: Val::Px(WIDTH),
+                height: Val::Px(HEIGHT),
+                ..default()
+            },
+            image: UiImage {
+                texture: assets.combo_bar_container.clone(),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+
+    let bar = commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Px(WIDTH),
+                height: Val::Px(HEIGHT),
+                ..default()
+            },
+            ..default()
+        })
+        .push_children(&[fill, container])
+        .id();
+
+    let text = commands
+        .spawn((
+            ComboText,
+            TextBundle {
+                text: Text::from_section(
+                    "",
+                    TextStyle {
+                        font: assets.font.clone(),
+                        font_size: FONT_SIZE,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_alignment(TextAlignment::Center),
+                style: Style {
+                    margin: UiRect {
+                        top: Val::Px(10.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id();
+
+    commands
+        .spawn((
+            ComboBar,
+            NodeBundle {
+                style: Style {
+                    top: Val::Px(40.0),
+                    right: Val::Px(40.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+        ))
+        .push_children(&[bar, text]);
+}
+
+fn despawn_combo_bar(mut commands: Commands, q_bars: Query<Entity, With<ComboBar>>) {
+    for entity in &q_bars {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn update_combo_bar(
+    combo: Res<Combo>,
+    mut q_bar: Query<&mut Visibility, With<ComboBar>>,
+    mut q_fill: Query<&mut Style, With<ComboBarFill>>,
+    mut q_text: Query<&mut Text, With<ComboText>>,
+) {
+    let mut visibility = match q_bar.get_single_mut() {
+        Ok(r) => r,
+        Err(_) => return,
+    };
+    let mut fill = match q_fill.get_single_mut() {
+        Ok(r) => r,
+        Err(_) => return,
+    };
+    let mut text = match q_text.get_single_mut() {
+        Ok(r) => r,
+        Err(_) => return,
+    };
+
+    *visibility = if combo.active() && combo.count() > 1 {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    fill.height = Val::Px((HEIGHT - 2.0 * PADDING) * combo.remaining());
+    text.sections[0].value = format!("{}\nx{:.1}", combo.count(), combo.multiplier());
+}
+
+pub struct ComboUiPlugin;
+
+impl Plugin for ComboUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (update_combo_bar,).in_set(GameplaySet))
+            .add_systems(OnEnter(GameState::Gaming), (spawn_combo_bar,))
+            .add_systems(OnExit(GameState::Gaming), (despawn_combo_bar,));
+    }
+}
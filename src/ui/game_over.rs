@@ -1,7 +1,8 @@
 use bevy::prelude::*;
 
 use crate::{
-    player::{input::PlayerInput, score::PlayerScore},
+    player::{input::PlayerInput, score::PlayerScore, stats::RunStats},
+    world::daily::{DailyAttempts, DailyDate},
     GameAssets, GameState,
 };
 
@@ -17,6 +18,8 @@ pub enum GameOverState {
     #[default]
     GameOver,
     Loading,
+    /// Submitting the score or fetching the leaderboard failed.
+    Error,
     Leaderboard,
 }
 
@@ -65,6 +68,22 @@ fn spawn_player_score(commands: &mut Commands, font: Handle<Font>, score: u32) -
     commands.spawn(text_bundle).id()
 }
 
+fn spawn_daily_text(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    date: &str,
+    attempts: u32,
+) -> Entity {
+    let text = format!("DAILY {}   ATTEMPT {}", date, attempts);
+    let text_style = TextStyle {
+        font,
+        font_size: 30.0,
+        color: Color::WHITE,
+    };
+    let text_bundle = TextBundle::from_sections([TextSection::new(text, text_style.clone())]);
+    commands.spawn(text_bundle).id()
+}
+
 fn spawn_prompt(commands: &mut Commands, font: Handle<Font>) -> Entity {
     let text = "ENTER NAME:";
     let text_style = TextStyle {
@@ -105,13 +124,107 @@ fn spawn_restart_text(commands: &mut Commands, font: Handle<Font>) -> Entity {
         .id()
 }
 
-fn spawn_text(commands: &mut Commands, font: Handle<Font>, score: u32) {
+fn spawn_stats_column(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    title: &str,
+    lines: Vec<String>,
+    left: bool,
+) {
+    let title_style = TextStyle {
+        font: font.clone(),
+        font_size: 30.0,
+        color: Color::WHITE,
+    };
+    let line_style = TextStyle {
+        font,
+        font_size: 20.0,
+        color: Color::rgb(0.8, 0.8, 0.8),
+    };
+
+    let mut children = vec![commands
+        .spawn(TextBundle::from_section(title, title_style))
+        .id()];
+    for line in lines {
+        children.push(
+            commands
+                .spawn(TextBundle::from_section(line, line_style.clone()))
+                .id(),
+        );
+    }
+
+    let (left, right) = if left {
+        (Val::Percent(5.0), Val::Auto)
+    } else {
+        (Val::Auto, Val::Percent(5.0))
+    };
+    commands
+        .spawn((
+            GameOverScreen,
+            NodeBundle {
+                style: Style {
+                    top: Val::Percent(30.0),
+                    left,
+                    right,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(8.0),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                z_index: ZIndex::Local(101),
+                ..default()
+            },
+        ))
+        .push_children(&children);
+}
+
+fn spawn_breakdown(commands: &mut Commands, font: Handle<Font>, run_stats: &RunStats) {
+    let mut kills: Vec<String> = run_stats
+        .kills_by_enemy
+        .iter()
+        .map(|(name, kills)| format!("{}: {}", name.replace('_', " ").to_uppercase(), kills))
+        .collect();
+    kills.push(String::new());
+    for (i, strike_kills) in run_stats.strike_kills.iter().enumerate() {
+        kills.push(format!("STRIKE {}: {}", i + 1, strike_kills));
+    }
+    kills.push(format!("DASH: {}", run_stats.dash_kills));
+    kills.push(format!("LANDING: {}", run_stats.landing_kills));
+    kills.push(format!("REFLECTION: {}", run_stats.reflection_kills));
+    kills.push(format!("CHARGE: {}", run_stats.charge_kills));
+    spawn_stats_column(commands, font.clone(), "KILLS", kills, true);
+
+    let run = vec![
+        format!("HIGHEST COMBO: {}", run_stats.highest_combo),
+        format!(
+            "LONGEST REFLECTION CHAIN: {}",
+            run_stats.longest_reflection_chain
+        ),
+        format!("HOOKS LANDED: {}", run_stats.hooks_landed),
+        format!("DASHES USED: {}", run_stats.dashes_used),
+        format!("DISTANCE: {:.0}", run_stats.distance_travelled),
+    ];
+    spawn_stats_column(commands, font, "RUN", run, false);
+}
+
+fn spawn_text(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    score: u32,
+    daily: Option<(String, u32)>,
+) {
     let title_text = spawn_title(commands, font.clone());
     let score_text = spawn_player_score(commands, font.clone(), score);
+    let daily_text =
+        daily.map(|(date, attempts)| spawn_daily_text(commands, font.clone(), &date, attempts));
     let prompt_text = spawn_prompt(commands, font.clone());
     let input_field = spawn_text_field(commands, font.clone());
     let restart_text = spawn_restart_text(commands, font.clone());
 
+    let mut children = vec![title_text, score_text];
+    children.extend(daily_text);
+    children.extend([prompt_text, input_field, restart_text]);
+
     commands
         .spawn((
             GameOverScreen,
@@ -129,22 +242,29 @@ fn spawn_text(commands: &mut Commands, font: Handle<Font>, score: u32) {
                 ..default()
             },
         ))
-        .push_children(&[
-            title_text,
-            score_text,
-            prompt_text,
-            input_field,
-            restart_text,
-        ]);
+        .push_children(&children);
 }
 
 fn spawn_game_over_screen(
     mut commands: Commands,
     assets: Res<GameAssets>,
     player_score: Res<PlayerScore>,
+    run_stats: Res<RunStats>,
+    daily_date: Res<DailyDate>,
+    daily_attempts: Res<DailyAttempts>,
 ) {
+    let daily = daily_date
+        .to_key()
+        .map(|date| (date, daily_attempts.attempts()));
+
     spawn_background(&mut commands, assets.white_pixel.clone());
-    spawn_text(&mut commands, assets.font.clone(), player_score.score());
+    spawn_text(
+        &mut commands,
+        assets.font.clone(),
+        player_score.score(),
+        daily,
+    );
+    spawn_breakdown(&mut commands, assets.font.clone(), &run_stats);
 }
 
 fn despawn_game_over_screens(
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{player::kill_counter::KillCounter, GameAssets, GameState};
+use crate::{player::kill_counter::KillCounter, GameAssets, GameState, GameplaySet};
 
 const SIZE: f32 = 32.0;
 
@@ -84,11 +84,8 @@ pub struct KillCounterPlugin;
 
 impl Plugin for KillCounterPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (update_counter_text,).run_if(in_state(GameState::Gaming)),
-        )
-        .add_systems(OnEnter(GameState::Gaming), (spawn_counter,))
-        .add_systems(OnExit(GameState::Gaming), (despawn_counter,));
+        app.add_systems(Update, (update_counter_text,).in_set(GameplaySet))
+            .add_systems(OnEnter(GameState::Gaming), (spawn_counter,))
+            .add_systems(OnExit(GameState::Gaming), (despawn_counter,));
     }
 }
@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+
+use crate::{player::input::PlayerInput, ui::game_over::GameOverState, GameAssets, GameState};
+
+use super::{
+    request::{LeaderboardError, RetryRequest},
+    LeaderboardData, LeaderboardTab, LeaderboardView,
+};
+
+#[derive(Component)]
+struct ErrorScreen;
+
+fn spawn_error_screen(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    leaderboard_error: Option<Res<LeaderboardError>>,
+) {
+    let text_style = TextStyle {
+        font: assets.font.clone(),
+        font_size: 45.0,
+        color: Color::WHITE,
+    };
+
+    let message = leaderboard_error
+        .map(|err| err.0.clone())
+        .unwrap_or_else(|| "SOMETHING WENT WRONG".to_string());
+    let error_text = commands
+        .spawn(TextBundle::from_sections([TextSection::new(
+            message,
+            TextStyle {
+                color: Color::rgb(1.0, 0.4, 0.4),
+                ..text_style.clone()
+            },
+        )]))
+        .id();
+
+    let options_text = commands
+        .spawn(TextBundle::from_sections([TextSection::new(
+            "R TO RETRY\nESC TO SKIP",
+            text_style,
+        )]))
+        .id();
+
+    commands
+        .spawn((
+            ErrorScreen,
+            NodeBundle {
+                style: Style {
+                    top: Val::Percent(35.0),
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Vh(20.0),
+                    align_items: AlignItems::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                z_index: ZIndex::Local(101),
+                ..default()
+            },
+        ))
+        .push_children(&[error_text, options_text]);
+}
+
+fn despawn_error_screen(mut commands: Commands, q_screens: Query<Entity, With<ErrorScreen>>) {
+    for entity in &q_screens {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn retry(player_input: Res<PlayerInput>, mut ev_retry_request: EventWriter<RetryRequest>) {
+    if player_input.restart {
+        ev_retry_request.send(RetryRequest);
+    }
+}
+
+/// Show the local high scores instead of the online leaderboard.
+fn skip(
+    mut commands: Commands,
+    player_input: Res<PlayerInput>,
+    mut view: ResMut<LeaderboardView>,
+    mut next_state: ResMut<NextState<GameOverState>>,
+) {
+    if !player_input.escape {
+        return;
+    }
+
+    commands.insert_resource(LeaderboardData::default());
+    *view = LeaderboardView {
+        tab: LeaderboardTab::Local,
+        offline: true,
+        ..default()
+    };
+    next_state.set(GameOverState::Leaderboard);
+}
+
+pub struct LeaderboardErrorScreenPlugin;
+
+impl Plugin for LeaderboardErrorScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (retry, skip)
+                .run_if(in_state(GameState::GameOver).and_then(in_state(GameOverState::Error))),
+        )
+        .add_systems(OnEnter(GameOverState::Error), spawn_error_screen)
+        .add_systems(OnExit(GameOverState::Error), despawn_error_screen);
+    }
+}
@@ -0,0 +1,272 @@
+use bevy::prelude::*;
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    player::{kill_counter::KillCounter, score::PlayerScore, speed_timer::SpeedTimer},
+    utils::{
+        format_time,
+        storage::{self, DisableStorage},
+    },
+    world::{daily::DailyDate, seed::Seed},
+    GameState,
+};
+
+use super::{
+    super::text_field::SubmittedTextInput, LeaderboardData, LeaderboardEntry, LeaderboardPeriod,
+    LEADERBOARD_COUNT,
+};
+
+const STORAGE_KEY: &str = "highscores";
+/// Only this many of the best runs are kept.
+const MAX_RUNS: usize = 50;
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LocalRun {
+    pub name: String,
+    pub score: u32,
+    pub kills: u32,
+    pub time: f32,
+    pub seed: u32,
+    pub date: String,
+    /// The date of the daily run, `None` for endless runs.
+    #[serde(default)]
+    pub daily: Option<String>,
+}
+
+/// The best runs on this device, sorted by score.
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct LocalHighscores {
+    runs: Vec<LocalRun>,
+    /// The index of the run that just ended, if it made it into the list.
+    #[serde(skip)]
+    last_run: Option<usize>,
+}
+
+impl LocalHighscores {
+    pub fn load() -> Self {
+        storage::load_data(STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        storage::save_data(STORAGE_KEY, self);
+    }
+
+    /// The best run of the daily run on `daily`, or the best endless run if it's `None`.
+    pub fn best(&self, daily: Option<&str>) -> Option<&LocalRun> {
+        self.runs.iter().find(|run| run.daily.as_deref() == daily)
+    }
+
+    fn add(&mut self, run: LocalRun) {
+        let index = self.runs.partition_point(|other| other.score >= run.score);
+        if index >= MAX_RUNS {
+            self.last_run = None;
+            return;
+        }
+
+        self.runs.insert(index, run);
+        self.runs.truncate(MAX_RUNS);
+        self.last_run = Some(index);
+    }
+
+    fn name_last_run(&mut self, name: &str) {
+        if let Some(index) = self.last_run {
+            self.runs[index].name = name.to_string();
+        }
+    }
+
+    /// The runs of the given period, starting at the rank `offset`.
+    /// Like `best`, daily and endless runs are listed separately.
+    pub(super) fn page(
+        &self,
+        period: LeaderboardPeriod,
+        offset: usize,
+        daily: Option<&str>,
+    ) -> LeaderboardData {
+        let today = Utc::now().date_naive();
+        let entries: Vec<(usize, LeaderboardEntry)> = self
+            .runs
+            .iter()
+            .enumerate()
+            .filter(|(_, run)| run.daily.as_deref() == daily)
+            .filter(|(_, run)| in_period(&run.date, period, today))
+            .enumerate()
+            .map(|(rank, (index, run))| {
+                let entry = LeaderboardEntry {
+                    id: None,
+                    rank,
+                    name: if run.name.is_empty() {
+                        "-".to_string()
+                    } else {
+                        run.name.clone()
+                    },
+                    score: run.score.to_string(),
+                    kills: run.kills.to_string(),
+                    time: format_time(run.time),
+                };
+                (index, entry)
+            })
+            .collect();
+
+        let own = entries
+            .iter()
+            .find(|(index, _)| Some(*index) == self.last_run)
+            .map(|(_, entry)| entry.clone());
+        LeaderboardData {
+            total: entries.len(),
+            entries: entries
+                .into_iter()
+                .skip(offset)
+                .take(LEADERBOARD_COUNT)
+                .map(|(_, entry)| entry)
+                .collect(),
+            own,
+        }
+    }
+}
+
+fn in_period(date: &str, period: LeaderboardPeriod, today: NaiveDate) -> bool {
+    let date = match NaiveDate::parse_from_str(date, DATE_FORMAT) {
+        Ok(r) => r,
+        Err(_) => return period == LeaderboardPeriod::AllTime,
+    };
+
+    match period {
+        LeaderboardPeriod::AllTime => true,
+        LeaderboardPeriod::Week => (today - date).num_days() < 7,
+        LeaderboardPeriod::Today => date == today,
+    }
+}
+
+fn record_run(
+    mut local_highscores: ResMut<LocalHighscores>,
+    player_score: Res<PlayerScore>,
+    kill_counter: Res<KillCounter>,
+    speed_timer: Res<SpeedTimer>,
+    seed: Res<Seed>,
+    daily_date: Res<DailyDate>,
+) {
+    // The name is filled in once the player submits it.
+    local_highscores.add(LocalRun {
+        name: String::new(),
+        score: player_score.score(),
+        kills: kill_counter.kills(),
+        time: speed_timer.elapsed,
+        seed: seed.0,
+        date: Utc::now().format(DATE_FORMAT).to_string(),
+        daily: daily_date.to_key(),
+    });
+}
+
+fn name_last_run(
+    mut local_highscores: ResMut<LocalHighscores>,
+    mut ev_submitted_text_input: EventReader<SubmittedTextInput>,
+) {
+    for ev in ev_submitted_text_input.read() {
+        local_highscores.name_last_run(&ev.0);
+    }
+}
+
+fn save_local_highscores(local_highscores: Res<LocalHighscores>) {
+    local_highscores.save();
+}
+
+pub struct LeaderboardLocalPlugin;
+
+impl Plugin for LeaderboardLocalPlugin {
+    fn build(&self, app: &mut App) {
+        if app.world.contains_resource::<DisableStorage>() {
+            app.init_resource::<LocalHighscores>();
+            return;
+        }
+
+        app.insert_resource(LocalHighscores::load())
+            .add_systems(OnEnter(GameState::GameOver), record_run)
+            .add_systems(
+                Update,
+                (
+                    name_last_run,
+                    // Inserting the loaded highscores counts as a change too,
+                    // there is no point in writing them back right away.
+                    save_local_highscores
+                        .after(name_last_run)
+                        .run_if(resource_changed::<LocalHighscores>())
+                        .run_if(not(resource_added::<LocalHighscores>())),
+                ),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(score: u32) -> LocalRun {
+        LocalRun {
+            name: String::new(),
+            score,
+            kills: 0,
+            time: 0.0,
+            seed: 0,
+            date: "2024-02-29".to_string(),
+            daily: None,
+        }
+    }
+
+    fn scores(highscores: &LocalHighscores) -> Vec<u32> {
+        highscores.runs.iter().map(|run| run.score).collect()
+    }
+
+    #[test]
+    fn runs_are_sorted_by_score() {
+        let mut highscores = LocalHighscores::default();
+        for score in [200, 500, 100, 300] {
+            highscores.add(run(score));
+        }
+        assert_eq!(scores(&highscores), vec![500, 300, 200, 100]);
+        assert_eq!(highscores.last_run, Some(1));
+    }
+
+    #[test]
+    fn equal_scores_keep_the_older_run_first() {
+        let mut highscores = LocalHighscores::default();
+        highscores.add(run(100));
+        highscores.add(run(100));
+        highscores.name_last_run("newer");
+
+        assert_eq!(highscores.last_run, Some(1));
+        assert_eq!(highscores.runs[0].name, "");
+        assert_eq!(highscores.runs[1].name, "newer");
+    }
+
+    #[test]
+    fn only_the_best_runs_are_kept() {
+        let mut highscores = LocalHighscores::default();
+        for score in 1..=MAX_RUNS as u32 {
+            highscores.add(run(score));
+        }
+
+        highscores.add(run(0));
+        assert_eq!(highscores.runs.len(), MAX_RUNS);
+        assert_eq!(highscores.last_run, None);
+
+        highscores.add(run(1000));
+        assert_eq!(highscores.runs.len(), MAX_RUNS);
+        assert_eq!(highscores.last_run, Some(0));
+        assert_eq!(highscores.runs[0].score, 1000);
+        assert_eq!(highscores.runs[MAX_RUNS - 1].score, 2);
+    }
+
+    #[test]
+    fn unplaced_runs_are_not_named() {
+        let mut highscores = LocalHighscores::default();
+        for score in 1..=MAX_RUNS as u32 {
+            highscores.add(run(score));
+        }
+        highscores.add(run(0));
+        highscores.name_last_run("tester");
+
+        assert!(highscores.runs.iter().all(|run| run.name.is_empty()));
+    }
+}
@@ -1,37 +1,119 @@
+mod error_screen;
 mod loading_screen;
+mod local;
+mod protocol;
 mod request;
 mod visual;
 
+pub use local::LocalHighscores;
+
 use bevy::prelude::*;
 
 use crate::{player::input::PlayerInput, GameState};
 
-use super::game_over::GameOverState;
+use super::{game_over::GameOverState, menu::MenuState};
 
+/// How many entries are shown on one page.
 const LEADERBOARD_COUNT: usize = 7;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 struct LeaderboardEntry {
+    /// The id the server gave the entry, local entries don't have one.
+    id: Option<u64>,
+    /// The rank starting at `0`.
+    rank: usize,
     name: String,
     score: String,
     kills: String,
     time: String,
 }
 
-#[derive(Resource, Deref, DerefMut)]
-struct LeaderboardData(Vec<LeaderboardEntry>);
+/// One page of the leaderboard.
+#[derive(Resource, Default, Clone)]
+struct LeaderboardData {
+    entries: Vec<LeaderboardEntry>,
+    /// How many entries there are on all pages.
+    total: usize,
+    /// The entry of the player, it may not be on this page.
+    own: Option<LeaderboardEntry>,
+}
 
 #[derive(Event)]
-struct DataFetched(String);
+struct DataFetched(LeaderboardData);
+
+/// Respawn the leaderboard with the current page.
+#[derive(Event)]
+struct RefreshLeaderboard;
+
+#[derive(Default, Clone, Copy, PartialEq)]
+enum LeaderboardTab {
+    #[default]
+    Online,
+    Local,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+enum LeaderboardPeriod {
+    #[default]
+    AllTime,
+    Week,
+    Today,
+}
+
+impl LeaderboardPeriod {
+    fn next(self) -> Self {
+        match self {
+            LeaderboardPeriod::AllTime => LeaderboardPeriod::Week,
+            LeaderboardPeriod::Week => LeaderboardPeriod::Today,
+            LeaderboardPeriod::Today => LeaderboardPeriod::AllTime,
+        }
+    }
 
-impl LeaderboardData {
-    pub fn find_index(&self, name: &str, score: &str) -> Option<usize> {
-        self.0
-            .iter()
-            .position(|entry| entry.name == name && entry.score == score)
+    fn label(self) -> &'static str {
+        match self {
+            LeaderboardPeriod::AllTime => "ALL TIME",
+            LeaderboardPeriod::Week => "THIS WEEK",
+            LeaderboardPeriod::Today => "TODAY",
+        }
     }
 }
 
+#[derive(Resource, Default)]
+struct LeaderboardView {
+    tab: LeaderboardTab,
+    period: LeaderboardPeriod,
+    /// The rank of the first entry on the current page.
+    offset: usize,
+    /// The online leaderboard couldn't be fetched.
+    offline: bool,
+}
+
+/// The page the online leaderboard should show, answered with `DataFetched`.
+#[derive(Event, Clone, Copy)]
+struct FetchPage {
+    period: LeaderboardPeriod,
+    offset: usize,
+}
+
+/// The offset of the page that contains `rank`.
+fn page_offset(rank: usize) -> usize {
+    rank - rank % LEADERBOARD_COUNT
+}
+
+/// The leaderboard in the main menu, it starts on the first page of the online tab.
+fn open_from_menu(
+    mut leaderboard_data: ResMut<LeaderboardData>,
+    mut view: ResMut<LeaderboardView>,
+    mut ev_fetch_page: EventWriter<FetchPage>,
+) {
+    *leaderboard_data = LeaderboardData::default();
+    *view = LeaderboardView::default();
+    ev_fetch_page.send(FetchPage {
+        period: view.period,
+        offset: view.offset,
+    });
+}
+
 fn restart(mut next_state: ResMut<NextState<GameState>>, player_input: Res<PlayerInput>) {
     if player_input.restart || player_input.escape {
         next_state.set(GameState::Restart);
@@ -43,11 +125,18 @@ pub struct LeaderboardPlugin;
 impl Plugin for LeaderboardPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<DataFetched>()
+            .add_event::<FetchPage>()
+            .add_event::<RefreshLeaderboard>()
+            .init_resource::<LeaderboardData>()
+            .init_resource::<LeaderboardView>()
             .add_plugins((
                 visual::LeaderboardVisualPlugin,
                 request::LeaderboardRequestPlugin,
+                local::LeaderboardLocalPlugin,
                 loading_screen::LeaderboardLoadingScreenPlugin,
+                error_screen::LeaderboardErrorScreenPlugin,
             ))
+            .add_systems(OnEnter(MenuState::Leaderboard), open_from_menu)
             .add_systems(
                 Update,
                 (restart,).run_if(
@@ -0,0 +1,78 @@
+//! Where the leaderboard server is and how its answers map to the leaderboard,
+//! the format itself is in `crate::protocol`.
+
+use bevy::prelude::*;
+
+use crate::{
+    protocol::{LeaderboardResponse, RemoteEntry},
+    utils::format_time,
+};
+
+use super::{LeaderboardData, LeaderboardEntry, LeaderboardPeriod};
+
+const DEFAULT_BASE_URL: &str = "https://rancic.org:3434";
+/// Environment variable to point the game at a different server, it's read at
+/// compile time (for wasm builds) and at runtime.
+const BASE_URL_ENV: &str = "INSTA_KILL_LEADERBOARD_URL";
+
+/// Where the leaderboard server lives.
+#[derive(Resource, Clone)]
+pub struct LeaderboardConfig {
+    pub base_url: String,
+}
+
+impl LeaderboardConfig {
+    /// Read the base url from the `--leaderboard-url <url>` command line argument,
+    /// falling back to the `INSTA_KILL_LEADERBOARD_URL` environment variable.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let base_url = args
+            .iter()
+            .position(|arg| arg == "--leaderboard-url")
+            .and_then(|i| args.get(i + 1).cloned())
+            .or_else(|| std::env::var(BASE_URL_ENV).ok())
+            .or_else(|| option_env!("INSTA_KILL_LEADERBOARD_URL").map(str::to_string))
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn leaderboard_url(&self) -> String {
+        format!("{}/leaderboard", self.base_url)
+    }
+}
+
+impl LeaderboardPeriod {
+    pub fn query(self) -> &'static str {
+        match self {
+            LeaderboardPeriod::AllTime => "all",
+            LeaderboardPeriod::Week => "week",
+            LeaderboardPeriod::Today => "today",
+        }
+    }
+}
+
+impl From<RemoteEntry> for LeaderboardEntry {
+    fn from(entry: RemoteEntry) -> Self {
+        LeaderboardEntry {
+            id: Some(entry.id),
+            rank: entry.rank,
+            name: entry.name,
+            score: entry.score.to_string(),
+            kills: entry.kills.to_string(),
+            time: format_time(entry.time),
+        }
+    }
+}
+
+impl From<LeaderboardResponse> for LeaderboardData {
+    fn from(response: LeaderboardResponse) -> Self {
+        LeaderboardData {
+            entries: response.entries.into_iter().map(Into::into).collect(),
+            total: response.total,
+            own: response.own.map(Into::into),
+        }
+    }
+}
@@ -1,123 +1,272 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
 use bevy::prelude::*;
 use bevy_mod_reqwest::*;
 
-use crate::player::{kill_counter::KillCounter, score::PlayerScore, speed_timer::SpeedTimer};
-use crate::utils::format_time;
+use crate::player::{
+    kill_counter::KillCounter, score::PlayerScore, speed_timer::SpeedTimer, stats::RunStats,
+};
+use crate::protocol::{LeaderboardResponse, ScoreSubmission, SubmissionResponse};
+use crate::replay::LastReplay;
+use crate::world::{daily::DailyDate, seed::Seed};
+use crate::GameState;
 
 use super::{
     super::{game_over::GameOverState, text_field::SubmittedTextInput},
-    DataFetched, LeaderboardData, LeaderboardEntry,
+    protocol::LeaderboardConfig,
+    DataFetched, FetchPage, LeaderboardData, LeaderboardTab, LeaderboardView, RefreshLeaderboard,
+    LEADERBOARD_COUNT,
 };
 
-const GET_URL: &str = "https://rancic.org/games/insta-kill/leaderboard.csv";
-const POST_URL: &str = "https://rancic.org:3434/leaderboard";
+/// Requests that didn't get a response after this many seconds count as failed.
+const REQUEST_TIMEOUT: f32 = 10.0;
 
 #[derive(Component)]
 struct PostRequest;
 #[derive(Component)]
 struct GetRequest;
+#[derive(Component, Deref, DerefMut)]
+struct RequestTimeout(Timer);
+
+impl Default for RequestTimeout {
+    fn default() -> Self {
+        Self(Timer::from_seconds(REQUEST_TIMEOUT, TimerMode::Once))
+    }
+}
 
+/// The last submitted score, kept around until it was accepted so that it can be retried.
+#[derive(Resource)]
+struct PendingSubmission(ScoreSubmission);
+/// The last requested page, to retry it if it fails.
+#[derive(Resource)]
+struct LastPage(FetchPage);
+/// The id the server gave the submitted score.
+#[derive(Resource)]
+struct OwnEntryId(u64);
+
+/// What went wrong with the last request, shown to the player.
+#[derive(Resource)]
+pub struct LeaderboardError(pub String);
+
+#[derive(Event)]
+struct RequestFailed(String);
+/// Send the request that failed again.
 #[derive(Event)]
-pub struct DataPosted;
+pub struct RetryRequest;
 
 fn trigger_loading(
     mut next_state: ResMut<NextState<GameOverState>>,
     mut ev_submitted_text_input: EventReader<SubmittedTextInput>,
+    mut ev_retry_request: EventReader<RetryRequest>,
 ) {
-    for _ in ev_submitted_text_input.read() {
+    if ev_submitted_text_input.read().count() + ev_retry_request.read().count() > 0 {
         next_state.set(GameOverState::Loading);
     }
 }
 
 fn trigger_leaderboard(
-    mut commands: Commands,
+    mut leaderboard_data: ResMut<LeaderboardData>,
+    state: Res<State<GameOverState>>,
     mut next_state: ResMut<NextState<GameOverState>>,
     mut ev_data_fetched: EventReader<DataFetched>,
+    mut ev_refresh_leaderboard: EventWriter<RefreshLeaderboard>,
 ) {
     for ev in ev_data_fetched.read() {
-        commands.insert_resource(string_to_leaderboard(&ev.0));
-        next_state.set(GameOverState::Leaderboard);
+        *leaderboard_data = ev.0.clone();
+        if *state.get() == GameOverState::Loading {
+            next_state.set(GameOverState::Leaderboard);
+        } else {
+            ev_refresh_leaderboard.send(RefreshLeaderboard);
+        }
     }
 }
 
-fn string_to_leaderboard(s: &str) -> LeaderboardData {
-    if s.is_empty() {
-        return LeaderboardData(Vec::new());
-    }
-
-    let mut result = Vec::new();
-    for row in s.split('\n') {
-        let values: Vec<&str> = row.split(',').collect();
-        let entry = if values.len() != 4 {
-            error!(
-                "The leaderboard entry doesn't have exaclty 4 entries, {:?}",
-                values
-            );
-            LeaderboardEntry::default()
+/// Show the error screen after a run, the leaderboard in the main menu falls back to the
+/// local high scores instead.
+fn trigger_error(
+    mut commands: Commands,
+    game_state: Res<State<GameState>>,
+    mut view: ResMut<LeaderboardView>,
+    mut next_state: ResMut<NextState<GameOverState>>,
+    mut ev_request_failed: EventReader<RequestFailed>,
+    mut ev_refresh_leaderboard: EventWriter<RefreshLeaderboard>,
+) {
+    for ev in ev_request_failed.read() {
+        commands.insert_resource(LeaderboardError(ev.0.clone()));
+        if *game_state.get() == GameState::GameOver {
+            next_state.set(GameOverState::Error);
         } else {
-            LeaderboardEntry {
-                name: values[0].to_string(),
-                score: values[1].to_string(),
-                kills: values[2].to_string(),
-                time: format_time(values[3].parse().unwrap_or_default()),
-            }
-        };
-        result.push(entry);
+            *view = LeaderboardView {
+                tab: LeaderboardTab::Local,
+                offline: true,
+                ..default()
+            };
+            ev_refresh_leaderboard.send(RefreshLeaderboard);
+        }
     }
-    LeaderboardData(result)
+}
+
+fn post_submission(
+    commands: &mut Commands,
+    reqwest: &ReqwestClient,
+    config: &LeaderboardConfig,
+    submission: &ScoreSubmission,
+) {
+    let body = match serde_json::to_string(submission) {
+        Ok(r) => r,
+        Err(err) => {
+            error!("failed to serialize score submission, {}", err);
+            return;
+        }
+    };
+
+    let req = match reqwest
+        .0
+        .post(config.leaderboard_url())
+        .header("Content-Type", "application/json")
+        .body(body)
+        .build()
+    {
+        Ok(r) => r,
+        Err(err) => {
+            error!("failed to build score submission, {}", err);
+            return;
+        }
+    };
+    commands.spawn((
+        ReqwestRequest::new(req),
+        PostRequest,
+        RequestTimeout::default(),
+    ));
 }
 
 fn send_post_request(
     mut commands: Commands,
     reqwest: Res<ReqwestClient>,
+    config: Res<LeaderboardConfig>,
     player_score: Res<PlayerScore>,
     kill_counter: Res<KillCounter>,
     speed_timer: Res<SpeedTimer>,
+    run_stats: Res<RunStats>,
+    seed: Res<Seed>,
+    daily_date: Res<DailyDate>,
+    last_replay: Option<Res<LastReplay>>,
     mut ev_submitted_text_input: EventReader<SubmittedTextInput>,
 ) {
     for ev in ev_submitted_text_input.read() {
-        let url = format!(
-            "{}/{}/{}/{}/{}",
-            POST_URL,
-            ev.0,
-            player_score.score(),
-            kill_counter.kills(),
-            speed_timer.elapsed
-        );
-
-        let req = reqwest.0.post(url).build().unwrap();
-        let req = ReqwestRequest::new(req);
-        commands.spawn((req, PostRequest));
+        let submission = ScoreSubmission {
+            name: ev.0.clone(),
+            score: player_score.score(),
+            kills: kill_counter.kills(),
+            time: speed_timer.elapsed,
+            seed: seed.0,
+            daily: daily_date.to_key(),
+            stats: run_stats.clone(),
+            // Attach the replay if we have one so that the server can verify the run.
+            replay: last_replay
+                .as_ref()
+                .map(|last_replay| STANDARD.encode(last_replay.to_bytes())),
+        };
+
+        post_submission(&mut commands, &reqwest, &config, &submission);
+        commands.insert_resource(PendingSubmission(submission));
+        commands.remove_resource::<OwnEntryId>();
     }
 }
 
-fn send_get_request(
+/// Post the score again if it wasn't accepted yet, otherwise fetch the last page again.
+fn retry_requests(
     mut commands: Commands,
     reqwest: Res<ReqwestClient>,
-    mut ev_data_posted: EventReader<DataPosted>,
+    config: Res<LeaderboardConfig>,
+    pending_submission: Option<Res<PendingSubmission>>,
+    last_page: Option<Res<LastPage>>,
+    mut ev_retry_request: EventReader<RetryRequest>,
+    mut ev_fetch_page: EventWriter<FetchPage>,
 ) {
-    if ev_data_posted.is_empty() {
-        return;
+    for _ in ev_retry_request.read() {
+        if let Some(pending_submission) = &pending_submission {
+            post_submission(&mut commands, &reqwest, &config, &pending_submission.0);
+        } else if let Some(last_page) = &last_page {
+            ev_fetch_page.send(last_page.0);
+        }
     }
-    ev_data_posted.clear();
+}
 
-    let req = reqwest.0.get(GET_URL).build().unwrap();
-    let req = ReqwestRequest::new(req);
-    commands.spawn((req, GetRequest));
+fn send_get_requests(
+    mut commands: Commands,
+    reqwest: Res<ReqwestClient>,
+    config: Res<LeaderboardConfig>,
+    own_entry_id: Option<Res<OwnEntryId>>,
+    daily_date: Res<DailyDate>,
+    mut ev_fetch_page: EventReader<FetchPage>,
+) {
+    for ev in ev_fetch_page.read() {
+        let mut query = vec![
+            ("period", ev.period.query().to_string()),
+            ("offset", ev.offset.to_string()),
+            ("limit", LEADERBOARD_COUNT.to_string()),
+        ];
+        if let Some(own_entry_id) = &own_entry_id {
+            query.push(("id", own_entry_id.0.to_string()));
+        }
+        if let Some(date) = daily_date.to_key() {
+            query.push(("daily", date));
+        }
+
+        let req = match reqwest
+            .0
+            .get(config.leaderboard_url())
+            .query(&query)
+            .build()
+        {
+            Ok(r) => r,
+            Err(err) => {
+                error!("failed to build leaderboard request, {}", err);
+                continue;
+            }
+        };
+        commands.spawn((
+            ReqwestRequest::new(req),
+            GetRequest,
+            RequestTimeout::default(),
+        ));
+        commands.insert_resource(LastPage(*ev));
+    }
 }
 
 fn handle_post_responses(
     mut commands: Commands,
     results: Query<(Entity, &ReqwestBytesResult), With<PostRequest>>,
-    mut ev_data_posted: EventWriter<DataPosted>,
+    mut view: ResMut<LeaderboardView>,
+    mut ev_fetch_page: EventWriter<FetchPage>,
+    mut ev_request_failed: EventWriter<RequestFailed>,
 ) {
     for (entity, res) in &results {
-        if let Err(err) = &res.0 {
-            error!("{}", err);
+        let response = match &res.0 {
+            Ok(bytes) => serde_json::from_slice::<SubmissionResponse>(bytes).map_err(|err| {
+                error!("failed to parse submission response, {}", err);
+                "THE SERVER REJECTED THE SCORE"
+            }),
+            Err(err) => {
+                error!("failed to upload score, {}", err);
+                Err("COULD NOT UPLOAD SCORE")
+            }
+        };
+
+        match response {
+            Ok(response) => {
+                commands.insert_resource(OwnEntryId(response.id));
+                commands.remove_resource::<PendingSubmission>();
+                *view = LeaderboardView::default();
+                ev_fetch_page.send(FetchPage {
+                    period: view.period,
+                    offset: view.offset,
+                });
+            }
+            Err(message) => ev_request_failed.send(RequestFailed(message.to_string())),
         }
 
         commands.entity(entity).despawn_recursive();
-        ev_data_posted.send(DataPosted);
     }
 }
 
@@ -125,18 +274,42 @@ fn handle_get_responses(
     mut commands: Commands,
     results: Query<(Entity, &ReqwestBytesResult), With<GetRequest>>,
     mut ev_data_fetched: EventWriter<DataFetched>,
+    mut ev_request_failed: EventWriter<RequestFailed>,
 ) {
     for (entity, res) in &results {
-        let data = match &res.0 {
-            Ok(bytes) => String::from_utf8(bytes.to_vec()).unwrap_or_default(),
+        let response = match &res.0 {
+            Ok(bytes) => serde_json::from_slice::<LeaderboardResponse>(bytes).map_err(|err| {
+                error!("failed to parse leaderboard, {}", err);
+                "THE LEADERBOARD IS BROKEN"
+            }),
             Err(err) => {
-                error!("{}", err);
-                String::new()
+                error!("failed to fetch leaderboard, {}", err);
+                Err("COULD NOT FETCH LEADERBOARD")
             }
         };
 
+        match response {
+            Ok(response) => ev_data_fetched.send(DataFetched(response.into())),
+            Err(message) => ev_request_failed.send(RequestFailed(message.to_string())),
+        }
+
         commands.entity(entity).despawn_recursive();
-        ev_data_fetched.send(DataFetched(data));
+    }
+}
+
+fn time_out_requests(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_requests: Query<(Entity, &mut RequestTimeout), Without<ReqwestBytesResult>>,
+    mut ev_request_failed: EventWriter<RequestFailed>,
+) {
+    for (entity, mut timeout) in &mut q_requests {
+        timeout.tick(time.delta());
+        if timeout.just_finished() {
+            warn!("leaderboard request timed out");
+            commands.entity(entity).despawn_recursive();
+            ev_request_failed.send(RequestFailed("THE SERVER DID NOT RESPOND".to_string()));
+        }
     }
 }
 
@@ -144,16 +317,23 @@ pub struct LeaderboardRequestPlugin;
 
 impl Plugin for LeaderboardRequestPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
-                send_post_request,
-                send_get_request,
-                handle_post_responses,
-                handle_get_responses,
-            ),
-        )
-        .add_event::<DataPosted>()
-        .add_systems(Update, (trigger_loading, trigger_leaderboard));
+        app.insert_resource(LeaderboardConfig::from_args())
+            .add_systems(
+                Update,
+                (
+                    send_post_request,
+                    retry_requests,
+                    send_get_requests.after(retry_requests),
+                    handle_post_responses,
+                    handle_get_responses,
+                    time_out_requests,
+                ),
+            )
+            .add_event::<RequestFailed>()
+            .add_event::<RetryRequest>()
+            .add_systems(
+                Update,
+                (trigger_loading, trigger_leaderboard, trigger_error),
+            );
     }
 }
@@ -1,24 +1,37 @@
 use bevy::prelude::*;
 
 use crate::{
-    player::score::PlayerScore, ui::text_field::SubmittedTextInput, GameAssets, GameState,
+    player::input::PlayerInput, ui::text_field::SubmittedTextInput, world::daily::DailyDate,
+    GameAssets, GameState,
 };
 
-use super::{super::game_over::GameOverState, LeaderboardData, LEADERBOARD_COUNT};
+use super::{
+    super::{game_over::GameOverState, menu::MenuState},
+    open_from_menu, page_offset, FetchPage, LeaderboardData, LeaderboardEntry, LeaderboardTab,
+    LeaderboardView, LocalHighscores, RefreshLeaderboard, LEADERBOARD_COUNT,
+};
 
 const FONT_SIZE: f32 = 25.0;
+const INACTIVE_TAB_COLOR: Color = Color::rgb(0.5, 0.5, 0.5);
+const OWN_ENTRY_COLOR: Color = Color::rgb(1.0, 0.8, 0.3);
 
 #[derive(Component)]
 struct Leaderboard;
 #[derive(Resource)]
 struct UserName(String);
 
-fn spawn_text(commands: &mut Commands, font: Handle<Font>, text: &str, i: usize) -> Entity {
+fn spawn_text(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    text: &str,
+    color: Color,
+    i: usize,
+) -> Entity {
     let pos = [-560.0, -450.0, 0.0, 200.0, 400.0];
     let text_style = TextStyle {
         font,
         font_size: FONT_SIZE,
-        color: Color::WHITE,
+        color,
     };
 
     commands
@@ -45,36 +58,68 @@ fn spawn_row(commands: &mut Commands, children: &[Entity]) -> Entity {
 }
 
 fn spawn_header(commands: &mut Commands, font: Handle<Font>) -> Entity {
-    let rank = spawn_text(commands, font.clone(), "", 0);
-    let name = spawn_text(commands, font.clone(), "NAME", 1);
-    let score = spawn_text(commands, font.clone(), "SCORE", 2);
-    let kill = spawn_text(commands, font.clone(), "KILLS", 3);
-    let time = spawn_text(commands, font.clone(), "TIME", 4);
+    let rank = spawn_text(commands, font.clone(), "", Color::WHITE, 0);
+    let name = spawn_text(commands, font.clone(), "NAME", Color::WHITE, 1);
+    let score = spawn_text(commands, font.clone(), "SCORE", Color::WHITE, 2);
+    let kill = spawn_text(commands, font.clone(), "KILLS", Color::WHITE, 3);
+    let time = spawn_text(commands, font.clone(), "TIME", Color::WHITE, 4);
 
     spawn_row(commands, &[rank, name, score, kill, time])
 }
 
+fn spawn_entry_row(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    entry: &LeaderboardEntry,
+    color: Color,
+) -> Entity {
+    let rank = spawn_text(
+        commands,
+        font.clone(),
+        &format!("{:03}", entry.rank + 1),
+        color,
+        0,
+    );
+    let name = spawn_text(commands, font.clone(), &entry.name, color, 1);
+    let score = spawn_text(commands, font.clone(), &entry.score, color, 2);
+    let kill = spawn_text(commands, font.clone(), &entry.kills, color, 3);
+    let time = spawn_text(commands, font.clone(), &entry.time, color, 4);
+
+    spawn_row(commands, &[rank, name, score, kill, time])
+}
+
+fn is_own(leaderboard_data: &LeaderboardData, entry: &LeaderboardEntry) -> bool {
+    leaderboard_data
+        .own
+        .as_ref()
+        .map_or(false, |own| own.rank == entry.rank && own.id == entry.id)
+}
+
 fn spawn_score_row(
     commands: &mut Commands,
     font: Handle<Font>,
-    leaderboard_data: &Res<LeaderboardData>,
+    leaderboard_data: &LeaderboardData,
     i: usize,
 ) -> Entity {
-    if i >= leaderboard_data.len() {
-        return commands.spawn(NodeBundle::default()).id();
-    }
-
-    let rank = spawn_text(commands, font.clone(), &format!("{:03}", i + 1), 0);
-    let name = spawn_text(commands, font.clone(), &leaderboard_data[i].name, 1);
-    let score = spawn_text(commands, font.clone(), &leaderboard_data[i].score, 2);
-    let kill = spawn_text(commands, font.clone(), &leaderboard_data[i].kills, 3);
-    let time = spawn_text(commands, font.clone(), &leaderboard_data[i].time, 4);
+    let entry = match leaderboard_data.entries.get(i) {
+        Some(r) => r,
+        None => return commands.spawn(NodeBundle::default()).id(),
+    };
 
-    spawn_row(commands, &[rank, name, score, kill, time])
+    let color = if is_own(leaderboard_data, entry) {
+        OWN_ENTRY_COLOR
+    } else {
+        Color::WHITE
+    };
+    spawn_entry_row(commands, font, entry, color)
 }
 
-fn spawn_restart_text(commands: &mut Commands, font: Handle<Font>) -> Entity {
-    let text = "ESC OR R TO RESTART";
+fn spawn_restart_text(commands: &mut Commands, font: Handle<Font>, in_menu: bool) -> Entity {
+    let text = if in_menu {
+        "ESC TO GO BACK"
+    } else {
+        "ESC OR R TO RESTART"
+    };
     let text_style = TextStyle {
         font,
         font_size: FONT_SIZE,
@@ -84,25 +129,88 @@ fn spawn_restart_text(commands: &mut Commands, font: Handle<Font>) -> Entity {
     commands.spawn(text_bundle).id()
 }
 
+/// The entry of the player below the table, unless it's already on the current page.
 fn spawn_last_row(
     commands: &mut Commands,
     font: Handle<Font>,
-    leaderboard_data: &Res<LeaderboardData>,
-    username: &str,
-    score: u32,
+    leaderboard_data: &LeaderboardData,
 ) -> Entity {
-    let i = match leaderboard_data.find_index(username, &score.to_string()) {
+    let own = match &leaderboard_data.own {
         Some(r) => r,
         None => return commands.spawn(NodeBundle::default()).id(),
     };
+    if leaderboard_data
+        .entries
+        .iter()
+        .any(|entry| is_own(leaderboard_data, entry))
+    {
+        return commands.spawn(NodeBundle::default()).id();
+    }
 
-    let rank = spawn_text(commands, font.clone(), &format!("{:03}", i + 1), 0);
-    let name = spawn_text(commands, font.clone(), &leaderboard_data[i].name, 1);
-    let score = spawn_text(commands, font.clone(), &leaderboard_data[i].score, 2);
-    let kill = spawn_text(commands, font.clone(), &leaderboard_data[i].kills, 3);
-    let time = spawn_text(commands, font.clone(), &leaderboard_data[i].time, 4);
+    spawn_entry_row(commands, font, own, OWN_ENTRY_COLOR)
+}
 
-    spawn_row(commands, &[rank, name, score, kill, time])
+fn spawn_tabs(commands: &mut Commands, font: Handle<Font>, view: &LeaderboardView) -> Entity {
+    let mut tab_text = |text: &str, active: bool| {
+        let color = if active {
+            Color::WHITE
+        } else {
+            INACTIVE_TAB_COLOR
+        };
+        let text_style = TextStyle {
+            font: font.clone(),
+            font_size: FONT_SIZE,
+            color,
+        };
+        commands
+            .spawn(TextBundle::from_sections([TextSection::new(
+                text, text_style,
+            )]))
+            .id()
+    };
+
+    let online = if view.offline {
+        tab_text("ONLINE (OFFLINE)", view.tab == LeaderboardTab::Online)
+    } else {
+        tab_text("ONLINE", view.tab == LeaderboardTab::Online)
+    };
+    let local = tab_text("LOCAL", view.tab == LeaderboardTab::Local);
+    let period = tab_text(view.period.label(), true);
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                column_gap: Val::Px(40.0),
+                ..default()
+            },
+            ..default()
+        })
+        .push_children(&[online, local, period])
+        .id()
+}
+
+fn spawn_page_text(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    leaderboard_data: &LeaderboardData,
+    view: &LeaderboardView,
+) -> Entity {
+    let pages = leaderboard_data.total.max(1).div_ceil(LEADERBOARD_COUNT);
+    let text = format!(
+        "PAGE {}/{}   TAB: SWITCH   F: FILTER   HOME: MY RANK",
+        view.offset / LEADERBOARD_COUNT + 1,
+        pages
+    );
+    let text_style = TextStyle {
+        font,
+        font_size: FONT_SIZE,
+        color: INACTIVE_TAB_COLOR,
+    };
+    commands
+        .spawn(TextBundle::from_sections([TextSection::new(
+            text, text_style,
+        )]))
+        .id()
 }
 
 fn spawn_buffer(commands: &mut Commands, buffer: f32) -> Entity {
@@ -125,50 +233,42 @@ fn spawn_buffer(commands: &mut Commands, buffer: f32) -> Entity {
         .id()
 }
 
-fn spawn_leaderboard(
-    mut commands: Commands,
-    assets: Res<GameAssets>,
-    leaderboard_data: Res<LeaderboardData>,
-    username: Res<UserName>,
-    player_score: Res<PlayerScore>,
+fn spawn_leaderboard_screen(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    leaderboard_data: &LeaderboardData,
+    view: &LeaderboardView,
+    in_menu: bool,
 ) {
-    let restart_text = spawn_restart_text(&mut commands, assets.font.clone());
-    let restart_buffer = spawn_buffer(&mut commands, 6.0);
-    let header = spawn_header(&mut commands, assets.font.clone());
-
-    let last_row_buffer = spawn_buffer(&mut commands, 6.0);
-    let last_row = spawn_last_row(
-        &mut commands,
-        assets.font.clone(),
-        &leaderboard_data,
-        &username.0,
-        player_score.score(),
-    );
+    let restart_text = spawn_restart_text(commands, font.clone(), in_menu);
+    let restart_buffer = spawn_buffer(commands, 6.0);
+    let tabs = spawn_tabs(commands, font.clone(), view);
+    let header = spawn_header(commands, font.clone());
+
+    let last_row_buffer = spawn_buffer(commands, 6.0);
+    let last_row = spawn_last_row(commands, font.clone(), leaderboard_data);
+    let page_text = spawn_page_text(commands, font.clone(), leaderboard_data, view);
 
     let mut score_rows = Vec::new();
     for i in 0..LEADERBOARD_COUNT {
-        score_rows.push(spawn_score_row(
-            &mut commands,
-            assets.font.clone(),
-            &leaderboard_data,
-            i,
-        ));
+        score_rows.push(spawn_score_row(commands, font.clone(), leaderboard_data, i));
     }
 
-    let mut children = vec![restart_text, restart_buffer, header];
+    let mut children = vec![restart_text, restart_buffer, tabs, header];
     children.extend(score_rows);
     children.push(last_row_buffer);
     children.push(last_row);
+    children.push(page_text);
 
     commands
         .spawn((
             Leaderboard,
             NodeBundle {
                 style: Style {
-                    top: Val::Percent(10.0),
+                    top: Val::Percent(8.0),
                     width: Val::Percent(100.0),
                     flex_direction: FlexDirection::Column,
-                    row_gap: Val::Vh(7.0),
+                    row_gap: Val::Vh(5.5),
                     align_items: AlignItems::Center,
                     position_type: PositionType::Absolute,
                     ..default()
@@ -180,6 +280,100 @@ fn spawn_leaderboard(
         .push_children(&children);
 }
 
+/// The page of the current tab, the online page is fetched by `request.rs`.
+fn current_page(
+    view: &LeaderboardView,
+    leaderboard_data: &LeaderboardData,
+    local_highscores: &LocalHighscores,
+    daily_date: &DailyDate,
+) -> LeaderboardData {
+    match view.tab {
+        LeaderboardTab::Online => leaderboard_data.clone(),
+        LeaderboardTab::Local => {
+            local_highscores.page(view.period, view.offset, daily_date.to_key().as_deref())
+        }
+    }
+}
+
+fn spawn_leaderboard(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    leaderboard_data: Res<LeaderboardData>,
+    local_highscores: Res<LocalHighscores>,
+    daily_date: Res<DailyDate>,
+    view: Res<LeaderboardView>,
+    menu_state: Res<State<MenuState>>,
+    q_leaderboards: Query<Entity, With<Leaderboard>>,
+) {
+    for entity in &q_leaderboards {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let data = current_page(&view, &leaderboard_data, &local_highscores, &daily_date);
+    let in_menu = *menu_state.get() == MenuState::Leaderboard;
+    spawn_leaderboard_screen(&mut commands, assets.font.clone(), &data, &view, in_menu);
+}
+
+fn navigate(
+    player_input: Res<PlayerInput>,
+    leaderboard_data: Res<LeaderboardData>,
+    local_highscores: Res<LocalHighscores>,
+    daily_date: Res<DailyDate>,
+    mut view: ResMut<LeaderboardView>,
+    mut ev_fetch_page: EventWriter<FetchPage>,
+    mut ev_refresh_leaderboard: EventWriter<RefreshLeaderboard>,
+) {
+    if !(player_input.previous_page
+        || player_input.next_page
+        || player_input.jump_to_rank
+        || player_input.switch_tab
+        || player_input.switch_filter
+        || player_input.scroll != 0.0)
+    {
+        return;
+    }
+
+    let data = current_page(&view, &leaderboard_data, &local_highscores, &daily_date);
+    let last_offset = page_offset(data.total.saturating_sub(1));
+
+    let offset = if player_input.previous_page || player_input.scroll < 0.0 {
+        view.offset.saturating_sub(LEADERBOARD_COUNT)
+    } else if player_input.next_page || player_input.scroll > 0.0 {
+        (view.offset + LEADERBOARD_COUNT).min(last_offset)
+    } else if player_input.jump_to_rank {
+        data.own
+            .as_ref()
+            .map_or(view.offset, |own| page_offset(own.rank))
+    } else {
+        view.offset
+    };
+
+    let (tab, period, offset) = if player_input.switch_tab {
+        let tab = match view.tab {
+            LeaderboardTab::Online => LeaderboardTab::Local,
+            LeaderboardTab::Local => LeaderboardTab::Online,
+        };
+        (tab, view.period, 0)
+    } else if player_input.switch_filter {
+        (view.tab, view.period.next(), 0)
+    } else {
+        (view.tab, view.period, offset)
+    };
+
+    if tab == view.tab && period == view.period && offset == view.offset {
+        return;
+    }
+
+    view.tab = tab;
+    view.period = period;
+    view.offset = offset;
+    if tab == LeaderboardTab::Online && !view.offline {
+        ev_fetch_page.send(FetchPage { period, offset });
+    } else {
+        ev_refresh_leaderboard.send(RefreshLeaderboard);
+    }
+}
+
 fn despawn_leaderboard(mut commands: Commands, q_leaderboards: Query<Entity, With<Leaderboard>>) {
     for entity in &q_leaderboards {
         commands.entity(entity).despawn_recursive();
@@ -204,6 +398,27 @@ impl Plugin for LeaderboardVisualPlugin {
             (spawn_leaderboard,)
                 .run_if(in_state(GameState::GameOver).and_then(resource_exists::<UserName>())),
         )
+        .add_systems(
+            Update,
+            (
+                navigate,
+                spawn_leaderboard
+                    .after(navigate)
+                    .run_if(on_event::<RefreshLeaderboard>()),
+            )
+                .run_if(
+                    in_state(GameState::GameOver)
+                        .and_then(in_state(GameOverState::Leaderboard))
+                        .and_then(resource_exists::<UserName>())
+                        .or_else(in_state(MenuState::Leaderboard)),
+                ),
+        )
+        .add_systems(
+            OnEnter(MenuState::Leaderboard),
+            (spawn_leaderboard.after(open_from_menu),),
+        )
+        .add_systems(OnExit(MenuState::Leaderboard), (despawn_leaderboard,))
+        .add_systems(OnExit(GameOverState::Leaderboard), (despawn_leaderboard,))
         .add_systems(OnExit(GameState::GameOver), (despawn_leaderboard,))
         .add_systems(Update, insert_username);
     }
@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+
+use crate::{
+    player::{
+        input::PlayerInput,
+        input_map::{InputAction, InputBinding, InputMap},
+    },
+    settings::Settings,
+    GameAssets,
+};
+
+use super::{
+    go_back, navigate_menu, spawn_menu, MenuButton, MenuButtonPressed, MenuScreen, MenuState,
+    RefreshMenu,
+};
+
+/// The actions that can be rebound, the menu actions stay fixed so that the menu can't break.
+const REBINDABLE_ACTIONS: [InputAction; 9] = [
+    InputAction::MoveUp,
+    InputAction::MoveDown,
+    InputAction::MoveLeft,
+    InputAction::MoveRight,
+    InputAction::Attack,
+    InputAction::Dash,
+    InputAction::Hook,
+    InputAction::Restart,
+    InputAction::ToggleFullscreen,
+];
+
+/// The action that gets the next pressed key or button.
+#[derive(Resource, Default)]
+struct Rebinding(Option<InputAction>);
+
+pub(super) fn rebinding(rebinding: Res<Rebinding>) -> bool {
+    rebinding.0.is_some()
+}
+
+fn action_label(action: InputAction) -> &'static str {
+    match action {
+        InputAction::MoveUp => "MOVE UP",
+        InputAction::MoveDown => "MOVE DOWN",
+        InputAction::MoveLeft => "MOVE LEFT",
+        InputAction::MoveRight => "MOVE RIGHT",
+        InputAction::Attack => "ATTACK",
+        InputAction::Dash => "DASH",
+        InputAction::Hook => "HOOK",
+        InputAction::Restart => "RESTART",
+        InputAction::ToggleFullscreen => "FULLSCREEN",
+        _ => "",
+    }
+}
+
+/// The first keyboard or mouse binding and the first gamepad binding of `action`.
+fn bindings_label(input_map: &InputMap, action: InputAction) -> String {
+    let label = |gamepad: bool| {
+        input_map
+            .bindings(action)
+            .iter()
+            .find(|binding| binding.is_gamepad() == gamepad)
+            .map_or("-".to_string(), InputBinding::label)
+    };
+    format!("{} / {}", label(false), label(true))
+}
+
+fn spawn_controls(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    settings: Res<Settings>,
+    rebinding: Res<Rebinding>,
+    q_screens: Query<Entity, With<MenuScreen>>,
+) {
+    let mut items: Vec<(MenuButton, String)> = REBINDABLE_ACTIONS
+        .iter()
+        .map(|&action| {
+            let bindings = if rebinding.0 == Some(action) {
+                "PRESS A KEY OR BUTTON".to_string()
+            } else {
+                bindings_label(&settings.input_map, action)
+            };
+            (
+                MenuButton::Bind(action),
+                format!("{}   {}", action_label(action), bindings),
+            )
+        })
+        .collect();
+    items.extend([
+        (MenuButton::ResetControls, "RESET".to_string()),
+        (MenuButton::Back, "BACK".to_string()),
+    ]);
+
+    spawn_menu(
+        &mut commands,
+        &q_screens,
+        assets.font.clone(),
+        "CONTROLS",
+        &items,
+    );
+}
+
+fn press_button(
+    mut rebinding: ResMut<Rebinding>,
+    mut settings: ResMut<Settings>,
+    mut ev_menu_button_pressed: EventReader<MenuButtonPressed>,
+    mut ev_refresh_menu: EventWriter<RefreshMenu>,
+) {
+    for ev in ev_menu_button_pressed.read() {
+        if ev.step != 0 {
+            continue;
+        }
+
+        match ev.button {
+            MenuButton::Bind(action) => rebinding.0 = Some(action),
+            MenuButton::ResetControls => settings.input_map = InputMap::default(),
+            _ => continue,
+        }
+        ev_refresh_menu.send(RefreshMenu);
+    }
+}
+
+/// Bind the next pressed key or button to the action that is being rebound, ESC cancels.
+fn capture_binding(
+    keys: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    player_input: Res<PlayerInput>,
+    mut rebinding: ResMut<Rebinding>,
+    mut settings: ResMut<Settings>,
+    mut ev_refresh_menu: EventWriter<RefreshMenu>,
+) {
+    let action = match rebinding.0 {
+        Some(r) => r,
+        None => return,
+    };
+
+    if player_input.escape {
+        rebinding.0 = None;
+        ev_refresh_menu.send(RefreshMenu);
+        return;
+    }
+
+    let binding = match keys
+        .get_just_pressed()
+        .next()
+        .map(|key| InputBinding::Key(*key))
+        .or_else(|| {
+            mouse_buttons
+                .get_just_pressed()
+                .next()
+                .map(|button| InputBinding::Mouse(*button))
+        })
+        .or_else(|| {
+            gamepad_buttons
+                .get_just_pressed()
+                .next()
+                .map(|button| InputBinding::Gamepad(button.button_type))
+        }) {
+        Some(r) => r,
+        None => return,
+    };
+
+    settings.input_map.rebind(action, binding);
+    rebinding.0 = None;
+    ev_refresh_menu.send(RefreshMenu);
+}
+
+fn reset_rebinding(mut rebinding: ResMut<Rebinding>) {
+    rebinding.0 = None;
+}
+
+pub struct ControlsMenuPlugin;
+
+impl Plugin for ControlsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Rebinding>()
+            .add_systems(OnEnter(MenuState::Controls), spawn_controls)
+            .add_systems(OnExit(MenuState::Controls), reset_rebinding)
+            .add_systems(
+                Update,
+                (
+                    // The key that started the rebinding must not be captured.
+                    capture_binding.after(navigate_menu).after(go_back),
+                    press_button.after(capture_binding),
+                    spawn_controls
+                        .after(press_button)
+                        .run_if(on_event::<RefreshMenu>()),
+                )
+                    .run_if(in_state(MenuState::Controls)),
+            );
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+use crate::{world::daily::GameMode, GameAssets, GameState};
+
+use super::{spawn_menu, MenuButton, MenuButtonPressed, MenuScreen, MenuState};
+
+fn spawn_main_menu(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    q_screens: Query<Entity, With<MenuScreen>>,
+) {
+    #[allow(unused_mut)]
+    let mut items = vec![
+        (MenuButton::Play, "PLAY".to_string()),
+        (MenuButton::Daily, "DAILY".to_string()),
+        (MenuButton::Settings, "SETTINGS".to_string()),
+        (MenuButton::Leaderboard, "LEADERBOARD".to_string()),
+    ];
+    #[cfg(not(target_arch = "wasm32"))]
+    items.push((MenuButton::Quit, "QUIT".to_string()));
+
+    spawn_menu(
+        &mut commands,
+        &q_screens,
+        assets.font.clone(),
+        "INSTA KILL",
+        &items,
+    );
+}
+
+fn press_button(
+    mut game_mode: ResMut<GameMode>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_state: ResMut<NextState<MenuState>>,
+    mut ev_menu_button_pressed: EventReader<MenuButtonPressed>,
+    #[cfg(not(target_arch = "wasm32"))] mut ev_app_exit: EventWriter<AppExit>,
+) {
+    for ev in ev_menu_button_pressed.read() {
+        if ev.step != 0 {
+            continue;
+        }
+
+        match ev.button {
+            MenuButton::Play => {
+                *game_mode = GameMode::Endless;
+                next_game_state.set(GameState::Restart);
+            }
+            MenuButton::Daily => {
+                *game_mode = GameMode::Daily;
+                next_game_state.set(GameState::Restart);
+            }
+            MenuButton::Settings => next_state.set(MenuState::Settings),
+            MenuButton::Leaderboard => next_state.set(MenuState::Leaderboard),
+            #[cfg(not(target_arch = "wasm32"))]
+            MenuButton::Quit => ev_app_exit.send(AppExit),
+            _ => {}
+        }
+    }
+}
+
+pub struct MainMenuPlugin;
+
+impl Plugin for MainMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::Main), spawn_main_menu)
+            .add_systems(Update, (press_button,).run_if(in_state(MenuState::Main)));
+    }
+}
@@ -0,0 +1,325 @@
+mod controls;
+mod main_menu;
+mod pause_menu;
+mod settings;
+
+use bevy::prelude::*;
+
+use crate::{
+    player::{input::PlayerInput, input_map::InputAction},
+    GameState, PauseState,
+};
+
+const TITLE_FONT_SIZE: f32 = 80.0;
+const ITEM_FONT_SIZE: f32 = 30.0;
+const ITEM_COLOR: Color = Color::WHITE;
+const SELECTED_COLOR: Color = Color::rgb(1.0, 0.8, 0.3);
+
+/// The screen of the main or pause menu that is shown.
+#[derive(States, Clone, Copy, Eq, PartialEq, Debug, Hash, Default)]
+pub enum MenuState {
+    #[default]
+    Closed,
+    Main,
+    Pause,
+    Settings,
+    Controls,
+    /// The leaderboard is spawned by the leaderboard plugin, the menu only adds the background.
+    Leaderboard,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum MenuButton {
+    Play,
+    Daily,
+    Settings,
+    Leaderboard,
+    #[cfg(not(target_arch = "wasm32"))]
+    Quit,
+    Resume,
+    Restart,
+    MainMenu,
+    Volume,
+    MusicVolume,
+    SoundVolume,
+    #[cfg(not(target_arch = "wasm32"))]
+    Fullscreen,
+    ScreenShake,
+    Vignette,
+    Controls,
+    Bind(InputAction),
+    ResetControls,
+    Back,
+}
+
+#[derive(Component)]
+struct MenuScreen;
+#[derive(Component)]
+struct MenuItem {
+    button: MenuButton,
+    /// The position from the top of the screen.
+    index: usize,
+}
+
+#[derive(Resource, Default)]
+struct MenuSelection(usize);
+
+/// An item was clicked or confirmed (`step == 0`) or changed with left/right (`step` is `-1` or `1`).
+#[derive(Event)]
+struct MenuButtonPressed {
+    button: MenuButton,
+    step: i32,
+}
+
+/// Respawn the current screen, for instance because a setting changed.
+#[derive(Event)]
+struct RefreshMenu;
+
+/// Spawn a menu screen, replacing the one that is currently shown.
+fn spawn_menu(
+    commands: &mut Commands,
+    q_screens: &Query<Entity, With<MenuScreen>>,
+    font: Handle<Font>,
+    title: &str,
+    items: &[(MenuButton, String)],
+) {
+    for entity in q_screens {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let title_text = commands
+        .spawn(TextBundle::from_sections([TextSection::new(
+            title,
+            TextStyle {
+                font: font.clone(),
+                font_size: TITLE_FONT_SIZE,
+                color: Color::WHITE,
+            },
+        )]))
+        .insert(Style {
+            margin: UiRect {
+                bottom: Val::Vh(4.0),
+                ..default()
+            },
+            ..default()
+        })
+        .id();
+
+    let mut children = vec![title_text];
+    for (index, (button, text)) in items.iter().enumerate() {
+        let text_style = TextStyle {
+            font: font.clone(),
+            font_size: ITEM_FONT_SIZE,
+            color: ITEM_COLOR,
+        };
+        let item = commands
+            .spawn((
+                MenuItem {
+                    button: *button,
+                    index,
+                },
+                Interaction::default(),
+                TextBundle::from_sections([TextSection::new(text.clone(), text_style)]),
+            ))
+            .id();
+        children.push(item);
+    }
+
+    spawn_background(commands, &children);
+}
+
+fn spawn_background(commands: &mut Commands, children: &[Entity]) {
+    commands
+        .spawn((
+            MenuScreen,
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Vh(2.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.1, 0.1, 0.1, 0.85)),
+                z_index: ZIndex::Local(100),
+                ..default()
+            },
+        ))
+        .push_children(children);
+}
+
+fn spawn_leaderboard_background(mut commands: Commands) {
+    spawn_background(&mut commands, &[]);
+}
+
+fn despawn_menu(mut commands: Commands, q_screens: Query<Entity, With<MenuScreen>>) {
+    for entity in &q_screens {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn reset_selection(mut selection: ResMut<MenuSelection>) {
+    selection.0 = 0;
+}
+
+fn open_main_menu(mut next_state: ResMut<NextState<MenuState>>) {
+    next_state.set(MenuState::Main);
+}
+
+fn open_pause_menu(mut next_state: ResMut<NextState<MenuState>>) {
+    next_state.set(MenuState::Pause);
+}
+
+fn close_menu(mut next_state: ResMut<NextState<MenuState>>) {
+    next_state.set(MenuState::Closed);
+}
+
+fn navigate_menu(
+    player_input: Res<PlayerInput>,
+    mut selection: ResMut<MenuSelection>,
+    q_items: Query<&MenuItem>,
+    q_interactions: Query<(&MenuItem, &Interaction), Changed<Interaction>>,
+    mut ev_menu_button_pressed: EventWriter<MenuButtonPressed>,
+) {
+    for (item, interaction) in &q_interactions {
+        match interaction {
+            Interaction::Pressed => {
+                selection.0 = item.index;
+                ev_menu_button_pressed.send(MenuButtonPressed {
+                    button: item.button,
+                    step: 0,
+                });
+            }
+            Interaction::Hovered => selection.0 = item.index,
+            Interaction::None => {}
+        }
+    }
+
+    let count = q_items.iter().count();
+    if count == 0 {
+        return;
+    }
+
+    if player_input.menu_up {
+        selection.0 = (selection.0 + count - 1) % count;
+    }
+    if player_input.menu_down {
+        selection.0 = (selection.0 + 1) % count;
+    }
+    selection.0 = selection.0.min(count - 1);
+
+    let step = if player_input.confirm {
+        0
+    } else if player_input.menu_left {
+        -1
+    } else if player_input.menu_right {
+        1
+    } else {
+        return;
+    };
+
+    if let Some(item) = q_items.iter().find(|item| item.index == selection.0) {
+        ev_menu_button_pressed.send(MenuButtonPressed {
+            button: item.button,
+            step,
+        });
+    }
+}
+
+fn highlight_selection(selection: Res<MenuSelection>, mut q_items: Query<(&MenuItem, &mut Text)>) {
+    for (item, mut text) in &mut q_items {
+        let color = if item.index == selection.0 {
+            SELECTED_COLOR
+        } else {
+            ITEM_COLOR
+        };
+        if text.sections[0].style.color != color {
+            text.sections[0].style.color = color;
+        }
+    }
+}
+
+/// Go to the previous screen with ESC or the back button.
+fn go_back(
+    player_input: Res<PlayerInput>,
+    state: Res<State<MenuState>>,
+    game_state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<MenuState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+    mut ev_menu_button_pressed: EventReader<MenuButtonPressed>,
+) {
+    let back_pressed = ev_menu_button_pressed
+        .read()
+        .filter(|ev| ev.button == MenuButton::Back && ev.step == 0)
+        .count()
+        > 0;
+    if !(player_input.escape || back_pressed) {
+        return;
+    }
+
+    match state.get() {
+        MenuState::Closed | MenuState::Main => {}
+        MenuState::Pause => next_pause_state.set(PauseState::Running),
+        MenuState::Settings => {
+            if *game_state.get() == GameState::Gaming {
+                next_state.set(MenuState::Pause);
+            } else {
+                next_state.set(MenuState::Main);
+            }
+        }
+        MenuState::Controls => next_state.set(MenuState::Settings),
+        MenuState::Leaderboard => next_state.set(MenuState::Main),
+    }
+}
+
+pub struct MenuPlugin;
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<MenuState>()
+            .init_resource::<MenuSelection>()
+            .add_event::<MenuButtonPressed>()
+            .add_event::<RefreshMenu>()
+            .add_plugins((
+                main_menu::MainMenuPlugin,
+                pause_menu::PauseMenuPlugin,
+                settings::SettingsMenuPlugin,
+                controls::ControlsMenuPlugin,
+            ))
+            .add_systems(OnEnter(GameState::MainMenu), open_main_menu)
+            .add_systems(OnExit(GameState::MainMenu), close_menu)
+            .add_systems(OnEnter(PauseState::Paused), open_pause_menu)
+            // Leaving the game for the main menu also unpauses it.
+            .add_systems(
+                OnExit(PauseState::Paused),
+                close_menu.run_if(not(in_state(GameState::MainMenu))),
+            )
+            .add_systems(
+                OnEnter(MenuState::Leaderboard),
+                spawn_leaderboard_background,
+            )
+            .add_systems(
+                Update,
+                (
+                    navigate_menu,
+                    go_back.after(navigate_menu),
+                    highlight_selection.after(navigate_menu),
+                )
+                    .run_if(not(in_state(MenuState::Closed)).and_then(not(controls::rebinding))),
+            );
+
+        for state in [
+            MenuState::Main,
+            MenuState::Pause,
+            MenuState::Settings,
+            MenuState::Controls,
+            MenuState::Leaderboard,
+        ] {
+            app.add_systems(OnEnter(state), reset_selection)
+                .add_systems(OnExit(state), despawn_menu);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::{GameAssets, GameState, PauseState};
+
+use super::{spawn_menu, MenuButton, MenuButtonPressed, MenuScreen, MenuState};
+
+fn spawn_pause_menu(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    q_screens: Query<Entity, With<MenuScreen>>,
+) {
+    let items = [
+        (MenuButton::Resume, "RESUME".to_string()),
+        (MenuButton::Restart, "RESTART".to_string()),
+        (MenuButton::Settings, "SETTINGS".to_string()),
+        (MenuButton::MainMenu, "MAIN MENU".to_string()),
+    ];
+    spawn_menu(
+        &mut commands,
+        &q_screens,
+        assets.font.clone(),
+        "PAUSED",
+        &items,
+    );
+}
+
+fn press_button(
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_pause_state: ResMut<NextState<PauseState>>,
+    mut next_state: ResMut<NextState<MenuState>>,
+    mut ev_menu_button_pressed: EventReader<MenuButtonPressed>,
+) {
+    for ev in ev_menu_button_pressed.read() {
+        if ev.step != 0 {
+            continue;
+        }
+
+        match ev.button {
+            MenuButton::Resume => next_pause_state.set(PauseState::Running),
+            MenuButton::Restart => next_game_state.set(GameState::Restart),
+            MenuButton::Settings => next_state.set(MenuState::Settings),
+            MenuButton::MainMenu => next_game_state.set(GameState::MainMenu),
+            _ => {}
+        }
+    }
+}
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::Pause), spawn_pause_menu)
+            .add_systems(Update, (press_button,).run_if(in_state(MenuState::Pause)));
+    }
+}
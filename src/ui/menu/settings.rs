@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+
+use crate::{settings::Settings, GameAssets};
+
+use super::{spawn_menu, MenuButton, MenuButtonPressed, MenuScreen, MenuState, RefreshMenu};
+
+const VOLUME_STEP: f64 = 0.1;
+const SCREEN_SHAKE_STEP: f64 = 0.25;
+
+fn percent(x: f64) -> String {
+    format!("{}%", (x * 100.0).round())
+}
+
+fn on_off(x: bool) -> &'static str {
+    if x {
+        "ON"
+    } else {
+        "OFF"
+    }
+}
+
+/// Left and right change the value by `step`, confirming increases it and wraps around at `max`.
+fn step_value(value: f64, step: i32, delta: f64, max: f64) -> f64 {
+    if step == 0 && value >= max {
+        0.0
+    } else if step == 0 {
+        (value + delta).min(max)
+    } else {
+        (value + step as f64 * delta).clamp(0.0, max)
+    }
+}
+
+fn spawn_settings(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    settings: Res<Settings>,
+    q_screens: Query<Entity, With<MenuScreen>>,
+) {
+    let mut items = vec![
+        (
+            MenuButton::Volume,
+            format!("VOLUME < {} >", percent(settings.main_volume)),
+        ),
+        (
+            MenuButton::MusicVolume,
+            format!("MUSIC < {} >", percent(settings.music_volume)),
+        ),
+        (
+            MenuButton::SoundVolume,
+            format!("SOUNDS < {} >", percent(settings.sound_volume)),
+        ),
+    ];
+    #[cfg(not(target_arch = "wasm32"))]
+    items.push((
+        MenuButton::Fullscreen,
+        format!("FULLSCREEN {}", on_off(settings.fullscreen)),
+    ));
+    items.extend([
+        (
+            MenuButton::ScreenShake,
+            format!("SCREEN SHAKE < {} >", percent(settings.screen_shake as f64)),
+        ),
+        (
+            MenuButton::Vignette,
+            format!("VIGNETTE {}", on_off(settings.vignette)),
+        ),
+        (MenuButton::Controls, "CONTROLS".to_string()),
+        (MenuButton::Back, "BACK".to_string()),
+    ]);
+
+    spawn_menu(
+        &mut commands,
+        &q_screens,
+        assets.font.clone(),
+        "SETTINGS",
+        &items,
+    );
+}
+
+fn press_button(
+    mut settings: ResMut<Settings>,
+    mut next_state: ResMut<NextState<MenuState>>,
+    mut ev_menu_button_pressed: EventReader<MenuButtonPressed>,
+    mut ev_refresh_menu: EventWriter<RefreshMenu>,
+) {
+    for ev in ev_menu_button_pressed.read() {
+        match ev.button {
+            MenuButton::Volume => {
+                settings.main_volume = step_value(settings.main_volume, ev.step, VOLUME_STEP, 1.0);
+            }
+            MenuButton::MusicVolume => {
+                settings.music_volume =
+                    step_value(settings.music_volume, ev.step, VOLUME_STEP, 1.0);
+            }
+            MenuButton::SoundVolume => {
+                settings.sound_volume =
+                    step_value(settings.sound_volume, ev.step, VOLUME_STEP, 1.0);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            MenuButton::Fullscreen => settings.fullscreen = !settings.fullscreen,
+            MenuButton::ScreenShake => {
+                settings.screen_shake = step_value(
+                    settings.screen_shake as f64,
+                    ev.step,
+                    SCREEN_SHAKE_STEP,
+                    1.0,
+                ) as f32;
+            }
+            MenuButton::Vignette => settings.vignette = !settings.vignette,
+            MenuButton::Controls if ev.step == 0 => {
+                next_state.set(MenuState::Controls);
+                continue;
+            }
+            _ => continue,
+        }
+        ev_refresh_menu.send(RefreshMenu);
+    }
+}
+
+pub struct SettingsMenuPlugin;
+
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::Settings), spawn_settings)
+            .add_systems(
+                Update,
+                (
+                    press_button,
+                    spawn_settings
+                        .after(press_button)
+                        .run_if(on_event::<RefreshMenu>()),
+                )
+                    .run_if(in_state(MenuState::Settings)),
+            );
+    }
+}
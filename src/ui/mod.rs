@@ -1,9 +1,11 @@
 pub mod world_text;
 
+mod combo;
 mod game_over;
 mod kill_counter;
 mod leaderboard;
 mod main_volume_bar;
+mod menu;
 mod score;
 mod text_field;
 mod vignette;
@@ -16,12 +18,14 @@ impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
             world_text::WorldTextPlugin,
+            combo::ComboUiPlugin,
             kill_counter::KillCounterPlugin,
             score::ScoreUiPlugin,
             game_over::GameOverPlugin,
             leaderboard::LeaderboardPlugin,
             text_field::TextFieldPlugin,
             main_volume_bar::MainVolumeBarPlugin,
+            menu::MenuPlugin,
             vignette::VignettePlugin,
         ));
     }
@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 
-use crate::{player::score::PlayerScore, GameAssets, GameState};
+use crate::{
+    player::score::PlayerScore, world::daily::DailyDate, GameAssets, GameState, GameplaySet,
+};
+
+use super::leaderboard::LocalHighscores;
 
 const SIZE: f32 = 32.0;
 
@@ -8,6 +12,8 @@ const SIZE: f32 = 32.0;
 struct Score;
 #[derive(Component)]
 struct ScoreText;
+#[derive(Component)]
+struct BestScoreText;
 
 fn spawn_score(mut commands: Commands, assets: Res<GameAssets>) {
     let icon = commands
@@ -46,6 +52,30 @@ fn spawn_score(mut commands: Commands, assets: Res<GameAssets>) {
         ))
         .id();
 
+    let best_text = commands
+        .spawn((
+            BestScoreText,
+            TextBundle {
+                text: Text::from_section(
+                    "",
+                    TextStyle {
+                        font: assets.font.clone(),
+                        font_size: SIZE / 2.0,
+                        color: Color::rgb(0.8, 0.8, 0.8),
+                    },
+                ),
+                style: Style {
+                    margin: UiRect {
+                        left: Val::Px(20.0),
+                        ..default()
+                    },
+                    ..default()
+                },
+                ..default()
+            },
+        ))
+        .id();
+
     commands
         .spawn((
             Score,
@@ -59,7 +89,7 @@ fn spawn_score(mut commands: Commands, assets: Res<GameAssets>) {
                 ..default()
             },
         ))
-        .push_children(&[icon, text]);
+        .push_children(&[icon, text, best_text]);
 }
 
 fn despawn_score(mut commands: Commands, q_counters: Query<Entity, With<Score>>) {
@@ -80,13 +110,31 @@ fn update_score_text(
     text.sections[0].value = player_score.score().to_string();
 }
 
+fn update_best_score_text(
+    player_score: Res<PlayerScore>,
+    local_highscores: Res<LocalHighscores>,
+    daily_date: Res<DailyDate>,
+    mut q_best_text: Query<&mut Text, With<BestScoreText>>,
+) {
+    let mut text = match q_best_text.get_single_mut() {
+        Ok(r) => r,
+        Err(_) => return,
+    };
+
+    let best = local_highscores
+        .best(daily_date.to_key().as_deref())
+        .map(|run| run.score)
+        .unwrap_or_default();
+    text.sections[0].value = format!("BEST {}", best.max(player_score.score()));
+}
+
 pub struct ScoreUiPlugin;
 
 impl Plugin for ScoreUiPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (update_score_text,).run_if(in_state(GameState::Gaming)),
+            (update_score_text, update_best_score_text).in_set(GameplaySet),
         )
         .add_systems(OnEnter(GameState::Gaming), (spawn_score,))
         .add_systems(OnExit(GameState::Gaming), (despawn_score,));
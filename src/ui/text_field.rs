@@ -3,7 +3,7 @@ use bevy::{
     prelude::*,
 };
 
-use crate::GameState;
+use crate::{settings::Settings, GameState};
 
 const TRANSPARENT_BACKGROUND: Color = Color::rgba(0.0, 0.0, 0.0, 0.7);
 const FONT_SIZE_INPUT: f32 = 32.0;
@@ -114,15 +114,27 @@ pub fn spawn_text_field(commands: &mut Commands, font: Handle<Font>) -> Entity {
         .id()
 }
 
-fn clear_buffer_text(mut typing_state: ResMut<TypingState>) {
-    typing_state.buf.clear();
+/// Start with the name that was entered last time.
+fn reset_buffer_text(settings: Res<Settings>, mut typing_state: ResMut<TypingState>) {
+    typing_state.buf = settings.username.clone();
+}
+
+fn remember_username(
+    mut settings: ResMut<Settings>,
+    mut ev_submitted_text_input: EventReader<SubmittedTextInput>,
+) {
+    for ev in ev_submitted_text_input.read() {
+        settings.username = ev.0.clone();
+    }
 }
 
 fn update_buffer_container(
     typing_state: Res<TypingState>,
     mut q_buffer_container: Query<&mut Style, With<InputField>>,
+    q_added: Query<(), Added<InputField>>,
 ) {
-    if !typing_state.is_changed() {
+    // The buffer may already hold the last name when the field gets spawned.
+    if !typing_state.is_changed() && q_added.is_empty() {
         return;
     }
 
@@ -138,8 +150,9 @@ fn update_buffer_container(
 fn update_buffer_text(
     typing_state: Res<TypingState>,
     mut q_typing_buffer_text: Query<&mut Text, With<TypingBuffer>>,
+    q_added: Query<(), Added<TypingBuffer>>,
 ) {
-    if !typing_state.is_changed() {
+    if !typing_state.is_changed() && q_added.is_empty() {
         return;
     }
 
@@ -271,7 +284,8 @@ impl Plugin for TextFieldPlugin {
         )))
         .init_resource::<TypingState>()
         .add_event::<SubmittedTextInput>()
-        .add_systems(OnEnter(GameState::GameOver), clear_buffer_text)
+        .add_systems(OnEnter(GameState::GameOver), reset_buffer_text)
+        .add_systems(Update, remember_username)
         .add_systems(
             Update,
             (
@@ -1,27 +1,55 @@
 use bevy::prelude::*;
 
-use crate::{GameAssets, GameState};
+use crate::{settings::Settings, GameAssets, GameState};
 
-fn spawn_vignette(mut commands: Commands, assets: Res<GameAssets>) {
-    commands.spawn(ImageBundle {
-        style: Style {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            ..default()
-        },
-        image: UiImage {
-            texture: assets.vignette.clone(),
+#[derive(Component)]
+struct Vignette;
+
+fn visibility(settings: &Settings) -> Visibility {
+    if settings.vignette {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    }
+}
+
+fn spawn_vignette(mut commands: Commands, assets: Res<GameAssets>, settings: Res<Settings>) {
+    commands.spawn((
+        Vignette,
+        ImageBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            image: UiImage {
+                texture: assets.vignette.clone(),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::BLACK),
+            visibility: visibility(&settings),
             ..default()
         },
-        background_color: BackgroundColor(Color::BLACK),
-        ..default()
-    });
+    ));
+}
+
+fn update_vignette(
+    settings: Res<Settings>,
+    mut q_vignettes: Query<&mut Visibility, With<Vignette>>,
+) {
+    for mut vis in &mut q_vignettes {
+        *vis = visibility(&settings);
+    }
 }
 
 pub struct VignettePlugin;
 
 impl Plugin for VignettePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnExit(GameState::AssetLoading), spawn_vignette);
+        app.add_systems(OnExit(GameState::AssetLoading), spawn_vignette)
+            .add_systems(
+                Update,
+                update_vignette.run_if(resource_changed::<Settings>()),
+            );
     }
 }
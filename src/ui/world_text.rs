@@ -2,7 +2,10 @@ use bevy::math::{cubic_splines::CubicCurve, vec2};
 use bevy::prelude::*;
 use rand::Rng;
 
-use crate::{GameAssets, GameState};
+use crate::{
+    world::{determinism::AddTickEvent, seed::EffectRng},
+    GameAssets, GameplaySet,
+};
 
 // This number will change the sharpness of the font.
 // The higher it is, the sharper the text.
@@ -54,14 +57,18 @@ impl Default for WorldText {
     }
 }
 
-fn spawn_world_text(commands: &mut Commands, assets: &Res<GameAssets>, ev: &SpawnWorldText) {
+fn spawn_world_text(
+    commands: &mut Commands,
+    assets: &Res<GameAssets>,
+    rng: &mut EffectRng,
+    ev: &SpawnWorldText,
+) {
     let text_style = TextStyle {
         font: assets.font.clone(),
         font_size: FONT_SCALE_RATIO,
         color: ev.world_text.font_color,
     };
 
-    let mut rng = rand::thread_rng();
     let rand_offset = Vec3::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0), 0.0)
         * ev.world_text.random_spray_intensity;
 
@@ -80,10 +87,11 @@ fn spawn_world_text(commands: &mut Commands, assets: &Res<GameAssets>, ev: &Spaw
 fn spawn_world_texts(
     mut commands: Commands,
     assets: Res<GameAssets>,
+    mut effect_rng: ResMut<EffectRng>,
     mut ev_spawn_world_text: EventReader<SpawnWorldText>,
 ) {
     for ev in ev_spawn_world_text.read() {
-        spawn_world_text(&mut commands, &assets, ev);
+        spawn_world_text(&mut commands, &assets, &mut effect_rng, ev);
     }
 }
 
@@ -120,11 +128,8 @@ pub struct WorldTextPlugin;
 
 impl Plugin for WorldTextPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (spawn_world_texts).run_if(in_state(GameState::Gaming)),
-        )
-        .add_systems(Update, (despawn_world_texts, animate_world_texts))
-        .add_event::<SpawnWorldText>();
+        app.add_systems(Update, (spawn_world_texts).in_set(GameplaySet))
+            .add_systems(Update, (despawn_world_texts, animate_world_texts))
+            .add_tick_event::<SpawnWorldText>();
     }
 }
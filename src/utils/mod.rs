@@ -1,4 +1,6 @@
-use bevy::prelude::*;
+pub mod storage;
+
+use bevy::{prelude::*, transform::TransformSystem};
 use bevy_rapier2d::{plugin::RapierTransformPropagateSet, prelude::*};
 
 pub const COLLISION_GROUPS_NONE: CollisionGroups = CollisionGroups::new(Group::NONE, Group::NONE);
@@ -7,9 +9,14 @@ pub struct UtilsPlugin;
 
 impl Plugin for UtilsPlugin {
     fn build(&self, app: &mut App) {
+        // Colliders need the fixed rotation before the physics step, sprites before they are drawn.
         app.add_systems(
-            PostUpdate,
+            FixedUpdate,
             reset_rotations.before(RapierTransformPropagateSet),
+        )
+        .add_systems(
+            PostUpdate,
+            reset_rotations.before(TransformSystem::TransformPropagate),
         );
     }
 }
@@ -58,3 +65,13 @@ pub fn format_time(seconds: f32) -> String {
 
     format!("{:02}:{:02}", minutes, seconds)
 }
+
+/// Rotate `rot` around the z axis towards `direction`, by at most `max_angle` radians.
+pub fn turn_towards(rot: Quat, direction: Vec2, max_angle: f32) -> Quat {
+    let current = rot.mul_vec3(Vec3::X).truncate();
+    if direction == Vec2::ZERO || current == Vec2::ZERO {
+        return rot;
+    }
+    let angle = current.angle_between(direction);
+    Quat::from_rotation_z(angle.clamp(-max_angle, max_angle)) * rot
+}
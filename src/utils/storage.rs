@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+use ron::ser::PrettyConfig;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Don't load or save anything of the player, for the headless tools and replay verification.
+#[derive(Resource)]
+pub struct DisableStorage;
+
+/// Where a value is stored on native, on wasm everything goes into the browsers local storage.
+#[derive(Clone, Copy)]
+enum Location {
+    /// Settings of the player, like the input map.
+    Config,
+    /// Things the game produced, like the local high scores.
+    Data,
+}
+
+/// Load a previously saved value.
+/// On native this is a RON file in the platform config directory,
+/// on wasm it's stored in the browsers local storage.
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    load_from(Location::Config, key)
+}
+
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    save_to(Location::Config, key, value);
+}
+
+/// Delete a value that is no longer used.
+pub fn remove(key: &str) {
+    remove_from(Location::Config, key);
+}
+
+/// Like `load`, but from the platform data directory on native.
+pub fn load_data<T: DeserializeOwned>(key: &str) -> Option<T> {
+    load_from(Location::Data, key)
+}
+
+/// Like `save`, but to the platform data directory on native.
+pub fn save_data<T: Serialize>(key: &str, value: &T) {
+    save_to(Location::Data, key, value);
+}
+
+fn load_from<T: DeserializeOwned>(location: Location, key: &str) -> Option<T> {
+    let content = read(location, key)?;
+    match ron::from_str(&content) {
+        Ok(r) => Some(r),
+        Err(err) => {
+            error!("failed to parse stored {}, {}", key, err);
+            None
+        }
+    }
+}
+
+fn save_to<T: Serialize>(location: Location, key: &str, value: &T) {
+    let content = match ron::ser::to_string_pretty(value, PrettyConfig::default()) {
+        Ok(r) => r,
+        Err(err) => {
+            error!("failed to serialize {}, {}", key, err);
+            return;
+        }
+    };
+    write(location, key, &content);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path(location: Location, key: &str) -> Option<std::path::PathBuf> {
+    let dir = match location {
+        Location::Config => dirs::config_dir(),
+        Location::Data => dirs::data_dir(),
+    };
+    dir.map(|dir| dir.join("insta-kill").join(format!("{}.ron", key)))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read(location: Location, key: &str) -> Option<String> {
+    std::fs::read_to_string(path(location, key)?).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write(location: Location, key: &str, content: &str) {
+    let path = match path(location, key) {
+        Some(r) => r,
+        None => {
+            warn!(
+                "there is no config or data directory on this platform, can't save {}",
+                key
+            );
+            return;
+        }
+    };
+
+    if let Some(parent) = path.parent() {
+        if let Err(err) = std::fs::create_dir_all(parent) {
+            error!("failed to create {}, {}", parent.display(), err);
+            return;
+        }
+    }
+
+    if let Err(err) = std::fs::write(&path, content) {
+        error!("failed to write {}, {}", path.display(), err);
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn remove_from(location: Location, key: &str) {
+    let path = match path(location, key) {
+        Some(r) => r,
+        None => return,
+    };
+
+    match std::fs::remove_file(&path) {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => error!("failed to remove {}, {}", path.display(), err),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read(_location: Location, key: &str) -> Option<String> {
+    local_storage()?
+        .get_item(&format!("insta-kill/{}", key))
+        .ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write(_location: Location, key: &str, content: &str) {
+    let storage = match local_storage() {
+        Some(r) => r,
+        None => {
+            warn!("local storage is not available, can't save {}", key);
+            return;
+        }
+    };
+
+    if storage
+        .set_item(&format!("insta-kill/{}", key), content)
+        .is_err()
+    {
+        error!("failed to write {} to local storage", key);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn remove_from(_location: Location, key: &str) {
+    if let Some(storage) = local_storage() {
+        if storage.remove_item(&format!("insta-kill/{}", key)).is_err() {
+            error!("failed to remove {} from local storage", key);
+        }
+    }
+}
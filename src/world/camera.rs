@@ -3,13 +3,15 @@ use bevy::render::camera::ScalingMode;
 #[cfg(not(target_arch = "wasm32"))]
 use bevy::render::view::screenshot::ScreenshotManager;
 #[cfg(not(target_arch = "wasm32"))]
-use bevy::window::{PrimaryWindow, WindowMode};
+use bevy::window::PrimaryWindow;
 use bevy_kira_audio::prelude::AudioReceiver;
 use bevy_rapier2d::dynamics::Velocity;
 
 use super::camera_shake::{update_camera, CameraShake};
 use crate::player::input::PlayerInput;
 use crate::player::Player;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::settings::Settings;
 use crate::world::world_debug::DebugMode;
 use crate::GameState;
 
@@ -71,26 +73,9 @@ fn zoom_camera(
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-fn toggle_full_screen(
-    mut main_window: Query<&mut Window, With<PrimaryWindow>>,
-    player_input: Res<PlayerInput>,
-) {
-    if !player_input.toggle_fullscreen {
-        return;
-    }
-
-    let mut window = match main_window.get_single_mut() {
-        Ok(w) => w,
-        Err(err) => {
-            error!("there is not exactly one window, {}", err);
-            return;
-        }
-    };
-
-    window.mode = if window.mode == WindowMode::Windowed {
-        WindowMode::Fullscreen
-    } else {
-        WindowMode::Windowed
+fn toggle_full_screen(player_input: Res<PlayerInput>, mut settings: ResMut<Settings>) {
+    if player_input.toggle_fullscreen {
+        settings.fullscreen = !settings.fullscreen;
     }
 }
 
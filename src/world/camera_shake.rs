@@ -1,7 +1,6 @@
 use chrono::Utc;
 
-use bevy::prelude::*;
-use bevy_rapier2d::plugin::RapierTransformPropagateSet;
+use bevy::{prelude::*, transform::TransformSystem};
 use noisy_bevy::simplex_noise_2d_seeded;
 
 use super::MainCamera;
@@ -10,13 +9,26 @@ const NOISE_STRENGTH: f32 = 10.0;
 const TRANSLATION_SHAKE_STRENGTH: f32 = 15.0;
 const ROTATION_SHAKE_STRENGTH: f32 = 2.5;
 
-#[derive(Resource, Default, Reflect)]
+#[derive(Resource, Reflect)]
 pub struct CameraShake {
+    /// Scales how far the camera shakes, `0.0` turns it off.
+    pub strength: f32,
     trauma: f32,
     seed: f32,
     target: Vec2,
 }
 
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            strength: 1.0,
+            trauma: 0.0,
+            seed: 0.0,
+            target: Vec2::ZERO,
+        }
+    }
+}
+
 impl CameraShake {
     #[allow(dead_code)]
     pub fn add_trauma(&mut self, trauma: f32) {
@@ -65,9 +77,11 @@ pub fn update_camera(
 
     let translation_offset = Vec3::new(shake.noise_value(0), shake.noise_value(1), 0.0)
         * shake.trauma.powi(2)
-        * TRANSLATION_SHAKE_STRENGTH;
+        * TRANSLATION_SHAKE_STRENGTH
+        * shake.strength;
     let rotation_offset = Quat::from_rotation_z(
-        (shake.noise_value(2) * shake.trauma.powi(2) * ROTATION_SHAKE_STRENGTH).to_radians(),
+        (shake.noise_value(2) * shake.trauma.powi(2) * ROTATION_SHAKE_STRENGTH * shake.strength)
+            .to_radians(),
     );
 
     transform.translation = shake.target.extend(transform.translation.z) + translation_offset;
@@ -82,7 +96,7 @@ impl Plugin for CameraShakePlugin {
             .init_resource::<CameraShake>()
             .add_systems(
                 PostUpdate,
-                update_camera.before(RapierTransformPropagateSet),
+                update_camera.before(TransformSystem::TransformPropagate),
             );
     }
 }
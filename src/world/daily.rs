@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+use chrono::{Datelike, NaiveDate};
+use rand_xoshiro::rand_core::{RngCore, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use serde::{Deserialize, Serialize};
+
+use crate::{utils::storage, GameState};
+
+const STORAGE_KEY: &str = "daily";
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Resource, Default, Clone, Copy, PartialEq, Debug)]
+pub enum GameMode {
+    /// Every run has a new seed.
+    #[default]
+    Endless,
+    /// Everyone plays the same seed on the same (UTC) day.
+    Daily,
+}
+
+impl GameMode {
+    /// Read the mode from the `--daily` command line argument.
+    pub fn from_args() -> Self {
+        if std::env::args().any(|arg| arg == "--daily") {
+            GameMode::Daily
+        } else {
+            GameMode::Endless
+        }
+    }
+}
+
+/// Use the given date for daily runs instead of today.
+#[derive(Resource, Default)]
+pub struct DateOverride(pub Option<NaiveDate>);
+
+/// The UTC date of the current daily run, `None` outside of the daily mode.
+/// It's set together with the `Seed` so that a run that goes past midnight stays on its day.
+#[derive(Resource, Default, Clone, Copy)]
+pub struct DailyDate(pub Option<NaiveDate>);
+
+impl DailyDate {
+    pub fn to_key(&self) -> Option<String> {
+        self.0.map(|date| date.format(DATE_FORMAT).to_string())
+    }
+}
+
+/// How often the player started the daily run of `date`.
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct DailyAttempts {
+    date: String,
+    attempts: u32,
+}
+
+impl DailyAttempts {
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+}
+
+/// The seed of the daily run on `date`.
+pub fn daily_seed(date: NaiveDate) -> u32 {
+    let day = date.year() as u64 * 10_000 + date.month() as u64 * 100 + date.day() as u64;
+    Xoshiro256PlusPlus::seed_from_u64(day).next_u32()
+}
+
+fn count_attempt(daily_date: Res<DailyDate>, mut daily_attempts: ResMut<DailyAttempts>) {
+    let date = match daily_date.to_key() {
+        Some(r) => r,
+        None => return,
+    };
+
+    if daily_attempts.date != date {
+        *daily_attempts = DailyAttempts { date, attempts: 0 };
+    }
+    daily_attempts.attempts += 1;
+    storage::save_data(STORAGE_KEY, &*daily_attempts);
+}
+
+pub struct DailyPlugin;
+
+impl Plugin for DailyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(storage::load_data::<DailyAttempts>(STORAGE_KEY).unwrap_or_default())
+            .add_systems(OnEnter(GameState::Gaming), count_attempt);
+    }
+}
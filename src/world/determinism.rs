@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::{event::event_update_system, schedule::ExecutorKind},
+    prelude::*,
+    time::{TimeSystem, TimeUpdateStrategy},
+    utils::Instant,
+};
+use bevy_rapier2d::prelude::*;
+
+use crate::GameplaySet;
+
+/// How many gameplay ticks there are in one second.
+pub const TICK_RATE: f64 = 60.0;
+
+/// The gameplay runs in `FixedUpdate`, one run is one tick of `1 / TICK_RATE` seconds.
+/// The virtual time only ever advances in whole ticks and by at most one tick per frame,
+/// so the animations see the same time steps as the gameplay, the physics use the same
+/// fixed timestep and `FixedUpdate` runs single threaded so that the order of its systems
+/// can't change between runs.
+/// Together with the `Seed` this means the same input will always result in the same run.
+///
+/// When frames take longer than a tick the game slows down instead of skipping ahead.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TickSet {
+    /// Before the physics step, replays apply and record the input of the tick here.
+    Input,
+    /// After the `GameplaySet`, for systems that react to the state changes of the tick.
+    Late,
+    /// The end of the tick, the input that was kept for the tick is consumed here.
+    Finish,
+}
+
+/// When active, every frame advances the game by exactly one tick, no matter how long
+/// the frame took. The headless tools use this to simulate runs as fast as possible.
+#[derive(Resource, Default, Clone, Copy)]
+pub struct FrameStepping {
+    pub active: bool,
+}
+
+/// Whether a tick ran since the tick events were last updated.
+#[derive(Resource, Default)]
+struct TickRan(bool);
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+struct TickEventUpdates;
+
+pub trait AddTickEvent {
+    /// Like `add_event`, but the events are only dropped after two ticks instead of two frames,
+    /// so that the next tick still sees them when there are frames without a tick in between.
+    fn add_tick_event<T: Event>(&mut self) -> &mut Self;
+}
+
+impl AddTickEvent for App {
+    fn add_tick_event<T: Event>(&mut self) -> &mut Self {
+        if !self.world.contains_resource::<Events<T>>() {
+            self.init_resource::<Events<T>>().add_systems(
+                First,
+                event_update_system::<T>
+                    .in_set(TickEventUpdates)
+                    .run_if(tick_ran),
+            );
+        }
+        self
+    }
+}
+
+pub fn tick_duration() -> Duration {
+    Duration::from_secs_f64(1.0 / TICK_RATE)
+}
+
+fn tick_ran(tick_ran: Res<TickRan>) -> bool {
+    tick_ran.0
+}
+
+fn mark_tick(mut tick_ran: ResMut<TickRan>) {
+    tick_ran.0 = true;
+}
+
+fn reset_tick_ran(mut tick_ran: ResMut<TickRan>) {
+    tick_ran.0 = false;
+}
+
+fn step_time(
+    frame_stepping: Res<FrameStepping>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+    mut last_frame: Local<Option<Instant>>,
+    mut behind: Local<Duration>,
+) {
+    let step = if frame_stepping.active {
+        tick_duration()
+    } else {
+        let now = Instant::now();
+        *behind += now - last_frame.unwrap_or(now);
+        *last_frame = Some(now);
+
+        if *behind >= tick_duration() {
+            // Never catch up on more than one tick.
+            *behind = (*behind - tick_duration()).min(tick_duration());
+            tick_duration()
+        } else {
+            Duration::ZERO
+        }
+    };
+    *time_update_strategy = TimeUpdateStrategy::ManualDuration(step);
+}
+
+fn configure_physics_timestep(mut rapier_config: ResMut<RapierConfiguration>) {
+    rapier_config.timestep_mode = TimestepMode::Fixed {
+        dt: tick_duration().as_secs_f32(),
+        substeps: 1,
+    };
+}
+
+pub struct DeterminismPlugin;
+
+impl Plugin for DeterminismPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FrameStepping>()
+            .init_resource::<TickRan>()
+            .insert_resource(Time::<Fixed>::from_duration(tick_duration()))
+            .edit_schedule(FixedUpdate, |schedule| {
+                schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+            })
+            .configure_sets(
+                FixedUpdate,
+                (
+                    TickSet::Input.before(PhysicsSet::SyncBackend),
+                    GameplaySet.after(PhysicsSet::Writeback),
+                    TickSet::Late.after(GameplaySet),
+                    TickSet::Finish.after(TickSet::Late),
+                ),
+            )
+            .add_systems(First, step_time.before(TimeSystem))
+            .add_systems(First, reset_tick_ran.after(TickEventUpdates))
+            .add_systems(FixedUpdate, mark_tick.in_set(TickSet::Finish))
+            .add_systems(Startup, configure_physics_timestep);
+    }
+}
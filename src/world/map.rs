@@ -1,11 +1,12 @@
 use rand::Rng;
 use rand_xoshiro::rand_core::SeedableRng;
+use rand_xoshiro::Xoshiro256PlusPlus;
 
 use bevy::prelude::*;
 use bevy_ecs_ldtk::prelude::*;
 
-use super::{seed::Seed, GameRng, BACKGROUND_ZINDEX_ABS, CHUNK_SIZE};
-use crate::{player::Player, GameAssets, GameState};
+use super::{seed::Seed, BACKGROUND_ZINDEX_ABS, CHUNK_SIZE};
+use crate::{player::Player, GameAssets, GameState, GameplaySet};
 
 const CAMERA_SIZE_X: f32 = 800.0;
 const CAMERA_SIZE_Y: f32 = 550.0;
@@ -43,7 +44,7 @@ fn map_indices_to_index(x_index: i32, y_index: i32, seed: u32) -> usize {
     let n = y_index.unsigned_abs() as u64;
 
     let seed: u64 = seed as u64 + m + n;
-    let mut rng = GameRng::seed_from_u64(seed);
+    let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
     rng.gen_range(0..IIDS.len())
 }
 
@@ -101,6 +102,13 @@ fn adjust_chunks(
     }
 }
 
+// Every run rolls a new seed, so the old chunks don't match anymore.
+fn despawn_chunks(mut commands: Commands, q_chunks: Query<Entity, With<Chunk>>) {
+    for entity in &q_chunks {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
 pub struct MapPlugin;
 
 impl Plugin for MapPlugin {
@@ -110,6 +118,7 @@ impl Plugin for MapPlugin {
                 level_spawn_behavior: LevelSpawnBehavior::UseZeroTranslation,
                 ..default()
             })
-            .add_systems(Update, (adjust_chunks).run_if(in_state(GameState::Gaming)));
+            .add_systems(Update, (adjust_chunks).in_set(GameplaySet))
+            .add_systems(OnEnter(GameState::Restart), despawn_chunks);
     }
 }
@@ -1,5 +1,8 @@
 pub mod camera;
 pub mod camera_shake;
+pub mod daily;
+pub mod determinism;
+pub mod pause;
 pub mod seed;
 pub mod world_debug;
 
@@ -16,8 +19,6 @@ use bevy_rapier2d::prelude::*;
 const BACKGROUND_ZINDEX_ABS: f32 = 1000.0;
 const CHUNK_SIZE: f32 = 32.0 * 32.0;
 
-pub type GameRng = rand_xoshiro::Xoshiro256PlusPlus;
-
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
@@ -25,8 +26,11 @@ impl Plugin for WorldPlugin {
         app.add_plugins((
             camera::CameraPlugin,
             camera_shake::CameraShakePlugin,
+            daily::DailyPlugin,
+            determinism::DeterminismPlugin,
             world_debug::WorldDebugPlugin,
             map::MapPlugin,
+            pause::PausePlugin,
             restart::RestartPlugin,
             seed::GameSeedPlugin,
         ))
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    player::input::PlayerInput, world::determinism::TickSet, GameState, GameplaySet, PauseState,
+};
+
+fn pause(player_input: Res<PlayerInput>, mut next_state: ResMut<NextState<PauseState>>) {
+    if player_input.escape {
+        next_state.set(PauseState::Paused);
+    }
+}
+
+fn freeze(mut rapier_config: ResMut<RapierConfiguration>, mut time: ResMut<Time<Virtual>>) {
+    rapier_config.physics_pipeline_active = false;
+    time.pause();
+}
+
+fn unfreeze(
+    mut rapier_config: ResMut<RapierConfiguration>,
+    mut time: ResMut<Time<Virtual>>,
+    mut player_input: ResMut<PlayerInput>,
+) {
+    rapier_config.physics_pipeline_active = true;
+    time.unpause();
+    // Clicks in the pause menu shouldn't carry over to the first tick.
+    player_input.consume_actions();
+}
+
+fn gameplay_running(
+    game_state: Res<State<GameState>>,
+    pause_state: Res<State<PauseState>>,
+) -> bool {
+    *game_state.get() == GameState::Gaming && *pause_state.get() == PauseState::Running
+}
+
+fn reset_pause_state(mut next_state: ResMut<NextState<PauseState>>) {
+    next_state.set(PauseState::Running);
+}
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<PauseState>()
+            .configure_sets(Update, GameplaySet.run_if(gameplay_running))
+            .configure_sets(
+                FixedUpdate,
+                (
+                    TickSet::Input.run_if(gameplay_running),
+                    GameplaySet.run_if(gameplay_running),
+                    TickSet::Late.run_if(gameplay_running),
+                ),
+            )
+            .add_systems(Update, (pause,).in_set(GameplaySet))
+            .add_systems(OnEnter(PauseState::Paused), freeze)
+            .add_systems(OnExit(PauseState::Paused), unfreeze)
+            .add_systems(OnExit(GameState::Gaming), reset_pause_state);
+    }
+}
@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 
 use crate::{
-    player::{kill_counter::KillCounter, score::PlayerScore, speed_timer::SpeedTimer},
+    player::{
+        combo::Combo, kill_counter::KillCounter, score::PlayerScore, speed_timer::SpeedTimer,
+        stats::RunStats,
+    },
     GameState,
 };
 
@@ -18,10 +21,14 @@ fn reset_resources(
     mut speed_timer: ResMut<SpeedTimer>,
     mut death_counter: ResMut<KillCounter>,
     mut player_score: ResMut<PlayerScore>,
+    mut combo: ResMut<Combo>,
+    mut run_stats: ResMut<RunStats>,
 ) {
     *speed_timer = SpeedTimer::default();
     *death_counter = KillCounter::default();
     *player_score = PlayerScore::default();
+    *combo = Combo::default();
+    *run_stats = RunStats::default();
 }
 
 fn restart(
@@ -1,14 +1,121 @@
+use rand_xoshiro::rand_core::SeedableRng;
+use rand_xoshiro::Xoshiro256PlusPlus;
+
 use bevy::prelude::*;
 use chrono::Utc;
 
-#[derive(Resource, Deref, DerefMut)]
+use crate::GameState;
+
+use super::daily::{daily_seed, DailyDate, DateOverride, GameMode};
+
+/// The seed of the current run, every source of randomness is derived from it.
+#[derive(Resource, Deref, DerefMut, Default, Clone, Copy)]
 pub struct Seed(pub u32);
 
+/// Use the given seed for every run instead of rolling a new one.
+/// This also turns daily runs into ordinary runs, they don't count for the daily leaderboard.
+#[derive(Resource, Default)]
+pub struct SeedOverride(pub Option<u32>);
+
+/// RNG for everything that affects the outcome of a run, like enemy spawn positions.
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng(Xoshiro256PlusPlus);
+
+/// RNG for purely visual and audio effects.
+/// It's kept separate from the `GameRng` so that effects that don't run
+/// (for instance sounds in a headless build) can't change the outcome of a run.
+#[derive(Resource, Deref, DerefMut)]
+pub struct EffectRng(Xoshiro256PlusPlus);
+
+impl SeedOverride {
+    /// Read the seed from the `--seed <seed>` command line argument.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let seed = args
+            .iter()
+            .position(|arg| arg == "--seed")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|seed| match seed.parse() {
+                Ok(r) => Some(r),
+                Err(err) => {
+                    error!("invalid seed '{}', {}", seed, err);
+                    None
+                }
+            });
+        Self(seed)
+    }
+}
+
+impl GameRng {
+    pub fn from_seed(seed: u32) -> Self {
+        Self(Xoshiro256PlusPlus::seed_from_u64(seed as u64))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_seed(0)
+    }
+}
+
+impl EffectRng {
+    pub fn from_seed(seed: u32) -> Self {
+        // Jump ahead so that we don't share a stream with the `GameRng`.
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed as u64);
+        rng.jump();
+        Self(rng)
+    }
+}
+
+impl Default for EffectRng {
+    fn default() -> Self {
+        Self::from_seed(0)
+    }
+}
+
+fn roll_seed() -> u32 {
+    (Utc::now().timestamp_millis().abs() & 0xFFFF_FFFF) as u32
+}
+
+fn reset_seed(
+    mut seed: ResMut<Seed>,
+    mut daily_date: ResMut<DailyDate>,
+    seed_override: Res<SeedOverride>,
+    date_override: Res<DateOverride>,
+    game_mode: Res<GameMode>,
+) {
+    daily_date.0 = match *game_mode {
+        GameMode::Daily if seed_override.0.is_none() => {
+            Some(date_override.0.unwrap_or_else(|| Utc::now().date_naive()))
+        }
+        _ => None,
+    };
+    seed.0 = seed_override
+        .0
+        .or_else(|| daily_date.0.map(daily_seed))
+        .unwrap_or_else(roll_seed);
+}
+
+fn reseed_rngs(seed: Res<Seed>, mut game_rng: ResMut<GameRng>, mut effect_rng: ResMut<EffectRng>) {
+    *game_rng = GameRng::from_seed(seed.0);
+    *effect_rng = EffectRng::from_seed(seed.0);
+}
+
 pub struct GameSeedPlugin;
 
 impl Plugin for GameSeedPlugin {
     fn build(&self, app: &mut App) {
-        let seed = (Utc::now().timestamp_millis().abs() & 0xFFFF_FFFF) as u32;
-        app.insert_resource(Seed(seed));
+        app.init_resource::<SeedOverride>()
+            .init_resource::<GameMode>()
+            .init_resource::<DateOverride>()
+            .init_resource::<DailyDate>()
+            .init_resource::<Seed>()
+            .init_resource::<GameRng>()
+            .init_resource::<EffectRng>()
+            .add_systems(Startup, (reset_seed, reseed_rngs).chain())
+            .add_systems(
+                OnEnter(GameState::Restart),
+                (reset_seed, reseed_rngs).chain(),
+            );
     }
 }
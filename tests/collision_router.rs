@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+
+use insta_kill::collision::{
+    CollisionRouterSet, HitEnemy, HookAttached, PlayerHit, ProjectileStruck,
+};
+use insta_kill::enemy::HitSource;
+use insta_kill::testing::TestApp;
+
+/// Every event of type `E` that the router sent.
+#[derive(Resource)]
+struct Routed<E: Event>(Vec<E>);
+
+fn collect<E: Event + Copy>(mut routed: ResMut<Routed<E>>, mut events: EventReader<E>) {
+    routed.0.extend(events.read().copied());
+}
+
+fn record<E: Event + Copy>(game: &mut TestApp) {
+    game.app()
+        .insert_resource(Routed::<E>(Vec::new()))
+        .add_systems(FixedUpdate, collect::<E>.after(CollisionRouterSet));
+}
+
+fn routed<E: Event + Copy>(game: &mut TestApp) -> Vec<E> {
+    game.app().world.resource::<Routed<E>>().0.clone()
+}
+
+#[test]
+fn strikes_are_routed_to_the_enemy_they_hit() {
+    let mut game = TestApp::new();
+    record::<HitEnemy>(&mut game);
+    let pos = game.player_pos() + Vec2::new(60.0, 0.0);
+    let bat = game.spawn_enemy("bat", pos);
+
+    game.strike(Vec2::X);
+    game.tick(10);
+
+    let hits = routed::<HitEnemy>(&mut game);
+    assert!(!hits.is_empty());
+    assert!(hits
+        .iter()
+        .all(|hit| hit.enemy == bat && hit.source == HitSource::Strike));
+}
+
+#[test]
+fn struck_projectiles_are_routed_and_reflections_hit_enemies() {
+    let mut game = TestApp::new();
+    record::<ProjectileStruck>(&mut game);
+    record::<HitEnemy>(&mut game);
+    let player_pos = game.player_pos();
+    let bat = game.spawn_enemy("bat", player_pos + Vec2::new(250.0, 0.0));
+    let projectile = game.spawn_archer_projectile(player_pos + Vec2::new(150.0, 0.0), Vec2::NEG_X);
+
+    game.strike(Vec2::X);
+    game.tick(30);
+
+    let struck = routed::<ProjectileStruck>(&mut game);
+    assert!(!struck.is_empty());
+    assert!(struck.iter().all(|ev| ev.projectile == projectile));
+
+    let hits = routed::<HitEnemy>(&mut game);
+    assert!(hits
+        .iter()
+        .any(|hit| hit.enemy == bat && hit.source == HitSource::Reflection));
+}
+
+#[test]
+fn touching_enemies_are_routed_as_player_hits() {
+    let mut game = TestApp::new();
+    record::<PlayerHit>(&mut game);
+    let pos = game.player_pos() + Vec2::new(0.0, -10.0);
+    let bat = game.spawn_enemy("bat", pos);
+
+    game.tick(5);
+
+    let hits = routed::<PlayerHit>(&mut game);
+    assert!(!hits.is_empty());
+    assert!(hits.iter().all(|hit| hit.by == bat));
+}
+
+#[test]
+fn hooks_are_routed_to_the_enemy_they_attach_to() {
+    let mut game = TestApp::new();
+    record::<HookAttached>(&mut game);
+    record::<HitEnemy>(&mut game);
+    let pos = game.player_pos() + Vec2::new(200.0, 0.0);
+    let bat = game.spawn_enemy("bat", pos);
+
+    game.hook(Vec2::X);
+    game.tick(30);
+
+    let attached = routed::<HookAttached>(&mut game);
+    assert!(!attached.is_empty());
+    assert!(attached.iter().all(|ev| ev.enemy == bat));
+    // The hook isn't an attack.
+    assert!(routed::<HitEnemy>(&mut game)
+        .iter()
+        .all(|hit| hit.source != HitSource::Hook));
+}
@@ -0,0 +1,490 @@
+use bevy::prelude::*;
+use chrono::NaiveDate;
+
+use insta_kill::enemy::{
+    HitSource, DASH_SCORE_MULTIPLIYER, FAMILY_CLEAR_SCORE_ADDITION,
+    REFLECTION_PROJECTILE_SCORE_ADDITION,
+};
+use insta_kill::player::{combo::Combo, speed_timer::SpeedTimer, state::PlayerState};
+use insta_kill::testing::TestApp;
+use insta_kill::world::{
+    camera::MainCamera,
+    daily::{daily_seed, DailyDate, DateOverride, GameMode},
+    seed::{Seed, SeedOverride},
+};
+use insta_kill::{GameState, PauseState};
+
+/// Tick until the player is in the given state, returns `false` if it didn't happen in time.
+fn tick_until_state(game: &mut TestApp, state: PlayerState, max_ticks: usize) -> bool {
+    for _ in 0..max_ticks {
+        if game.player().state == state {
+            return true;
+        }
+        game.tick(1);
+    }
+    game.player().state == state
+}
+
+#[test]
+fn strike_kills_enemy() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(60.0, 0.0);
+    let bat = game.spawn_enemy("bat", pos);
+    let bat_score = game.enemy(bat).unwrap().score;
+
+    game.strike(Vec2::X);
+    game.tick(10);
+
+    assert_eq!(game.kills(), 1);
+    assert_eq!(game.score(), bat_score);
+    assert!(game.enemy(bat).is_none());
+    assert!(!game.player().disabled);
+}
+
+#[test]
+fn strike_misses_enemy_behind_player() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(-300.0, 0.0);
+    game.spawn_enemy("bat", pos);
+
+    game.strike(Vec2::X);
+    game.tick(10);
+
+    assert_eq!(game.kills(), 0);
+    assert_eq!(game.score(), 0);
+}
+
+#[test]
+fn dash_kill_applies_score_multiplier() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(150.0, 0.0);
+    let bat = game.spawn_enemy("bat", pos);
+    let bat_score = game.enemy(bat).unwrap().score;
+
+    game.dash(Vec2::X);
+    game.tick(1);
+    assert_eq!(game.player().state, PlayerState::Dashing);
+    game.tick(20);
+
+    assert_eq!(game.kills(), 1);
+    assert_eq!(
+        game.score(),
+        (bat_score as f32 * DASH_SCORE_MULTIPLIYER) as u32
+    );
+    assert!(!game.player().disabled);
+}
+
+#[test]
+fn reflection_projectile_chain_increases_score() {
+    let mut game = TestApp::new();
+    let player_pos = game.player_pos();
+    let first = game.spawn_enemy("bat", player_pos + Vec2::new(250.0, 0.0));
+    let second = game.spawn_enemy("bat", player_pos + Vec2::new(400.0, 0.0));
+    let first_score = game.enemy(first).unwrap().score;
+    let second_score = game.enemy(second).unwrap().score;
+
+    game.spawn_archer_projectile(player_pos + Vec2::new(150.0, 0.0), Vec2::NEG_X);
+    game.strike(Vec2::X);
+    game.tick(30);
+
+    // Every enemy that the projectile passes through is worth more than the last.
+    assert_eq!(game.kills(), 2);
+    assert_eq!(
+        game.score(),
+        first_score + second_score + REFLECTION_PROJECTILE_SCORE_ADDITION * (1 + 2)
+    );
+    assert!(!game.player().disabled);
+}
+
+#[test]
+fn hook_slides_player_to_enemy() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(200.0, 0.0);
+    let bat = game.spawn_enemy("bat", pos);
+
+    game.hook(Vec2::X);
+    assert!(tick_until_state(&mut game, PlayerState::Hooking, 2));
+    assert!(tick_until_state(&mut game, PlayerState::Sliding, 30));
+    assert!(game.enemy(bat).unwrap().stunned);
+
+    assert!(tick_until_state(&mut game, PlayerState::Idling, 30));
+    assert!(game.player_pos().distance(pos) < 150.0);
+}
+
+#[test]
+fn hook_without_target_returns_to_idle() {
+    let mut game = TestApp::new();
+
+    game.hook(Vec2::X);
+    assert!(tick_until_state(&mut game, PlayerState::Hooking, 2));
+    assert!(tick_until_state(&mut game, PlayerState::Idling, 60));
+}
+
+#[test]
+fn touching_enemy_ends_run() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(0.0, -10.0);
+    game.spawn_enemy("bat", pos);
+
+    game.tick(5);
+
+    assert_eq!(game.state(), GameState::GameOver);
+    assert_eq!(game.kills(), 0);
+}
+
+#[test]
+fn charger_kills_enemies_in_its_way() {
+    let mut game = TestApp::new();
+    let player_pos = game.player_pos();
+    let bat = game.spawn_enemy("bat", player_pos + Vec2::new(120.0, 0.0));
+    let bat_score = game.enemy(bat).unwrap().score;
+    // Keep the bat in the way of the charge.
+    game.enemy_mut(bat).unwrap().stunned = true;
+    game.spawn_enemy("charger", player_pos + Vec2::new(260.0, 0.0));
+
+    for _ in 0..120 {
+        if game.enemy(bat).is_none() {
+            break;
+        }
+        game.tick(1);
+    }
+
+    assert!(game.enemy(bat).is_none());
+    assert_eq!(game.kills(), 1);
+    assert_eq!(game.score(), bat_score);
+    assert_eq!(game.combo().count(), 0);
+}
+
+#[test]
+fn charger_does_not_steal_kills_from_the_player() {
+    fn spawn(game: &mut TestApp, with_charger: bool) -> Entity {
+        let player_pos = game.player_pos();
+        let bat = game.spawn_enemy("bat", player_pos + Vec2::new(60.0, 0.0));
+        game.enemy_mut(bat).unwrap().stunned = true;
+        if with_charger {
+            game.spawn_enemy("charger", player_pos + Vec2::new(200.0, 0.0));
+        }
+        bat
+    }
+
+    fn ticks_until_dead(game: &mut TestApp, enemy: Entity) -> usize {
+        for ticks in 0..120 {
+            if game.enemy(enemy).is_none() {
+                return ticks;
+            }
+            game.tick(1);
+        }
+        panic!("the enemy didn't die in time");
+    }
+
+    // How long a strike takes to kill the bat, and when the charger runs it over on its own.
+    let mut game = TestApp::new();
+    let bat = spawn(&mut game, false);
+    game.strike(Vec2::X);
+    let strike_ticks = ticks_until_dead(&mut game, bat);
+
+    let mut game = TestApp::new();
+    let bat = spawn(&mut game, true);
+    let charge_ticks = ticks_until_dead(&mut game, bat);
+    assert_eq!(game.run_stats().charge_kills, 1);
+
+    // Strike the bat so that it dies on the tick the charger runs into it.
+    let mut game = TestApp::new();
+    let bat = spawn(&mut game, true);
+    game.tick(charge_ticks - strike_ticks);
+    game.strike(Vec2::X);
+    assert_eq!(ticks_until_dead(&mut game, bat), strike_ticks);
+    assert_eq!(game.run_stats().charge_kills, 0);
+    assert!(game.run_stats().strike_kills.iter().sum::<u32>() >= 1);
+}
+
+#[test]
+fn hook_passes_through_charger_that_is_not_recovering() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(200.0, 0.0);
+    let charger = game.spawn_enemy("charger", pos);
+
+    game.hook(Vec2::X);
+    assert!(tick_until_state(&mut game, PlayerState::Hooking, 2));
+    assert!(tick_until_state(&mut game, PlayerState::Idling, 60));
+    assert!(!game.enemy(charger).unwrap().stunned);
+}
+
+#[test]
+fn splitter_splits_and_clearing_the_family_gives_a_bonus() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(400.0, 0.0);
+    let splitter = game.spawn_enemy("splitter", pos);
+    let splitter_score = game.enemy(splitter).unwrap().score;
+
+    game.enemy_mut(splitter).unwrap().disabled = true;
+    game.tick(2);
+
+    let children = game.enemies();
+    assert!((2..=3).contains(&children.len()));
+    let family = game.enemy(children[0]).unwrap().family;
+    assert!(family.is_some());
+    let mut children_score = 0;
+    for &child in &children {
+        let enemy = game.enemy(child).unwrap();
+        assert_eq!(enemy.generation, 1);
+        assert_eq!(enemy.family, family);
+        children_score += enemy.score;
+    }
+    assert_eq!(game.score(), splitter_score);
+
+    for &child in &children {
+        game.enemy_mut(child).unwrap().disabled = true;
+    }
+    game.tick(2);
+
+    assert!(game.enemies().is_empty());
+    assert_eq!(game.kills(), 1 + children.len() as u32);
+    assert_eq!(
+        game.score(),
+        splitter_score + children_score + FAMILY_CLEAR_SCORE_ADDITION
+    );
+}
+
+#[test]
+fn shield_deflects_strikes_but_not_dashes() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(60.0, 0.0);
+    let shielded = game.spawn_enemy("shielded", pos);
+    // Let the shield turn towards the player.
+    game.tick(1);
+
+    game.strike(Vec2::X);
+    game.tick(10);
+    assert!(game.enemy(shielded).is_some());
+    assert_eq!(game.kills(), 0);
+
+    game.dash(Vec2::X);
+    game.tick(20);
+    assert!(game.enemy(shielded).is_none());
+    assert_eq!(game.kills(), 1);
+    assert!(!game.player().disabled);
+}
+
+#[test]
+fn boss_only_dies_after_its_weak_point_was_hooked() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(100.0, 0.0);
+    let boss = game.spawn_enemy("boss", pos);
+
+    game.strike(Vec2::X);
+    game.tick(10);
+    assert!(game.enemy(boss).is_some());
+    assert_eq!(game.kills(), 0);
+
+    game.hook(Vec2::X);
+    assert!(tick_until_state(&mut game, PlayerState::Hooking, 2));
+    assert!(tick_until_state(&mut game, PlayerState::Idling, 30));
+    assert!(game.enemy(boss).unwrap().stunned);
+
+    game.strike(Vec2::X);
+    game.tick(10);
+    assert!(game.enemy(boss).is_none());
+    assert_eq!(game.kills(), 1);
+    assert!(!game.player().disabled);
+}
+
+#[test]
+fn elite_archer_shoots_a_spread() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(400.0, 0.0);
+    game.spawn_enemy("elite_archer", pos);
+
+    for _ in 0..180 {
+        if !game.projectiles().is_empty() {
+            break;
+        }
+        game.tick(1);
+    }
+
+    assert_eq!(game.projectiles().len(), 5);
+}
+
+#[test]
+fn bats_spread_out_instead_of_stacking() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(600.0, 0.0);
+    let first = game.spawn_enemy("bat", pos);
+    let second = game.spawn_enemy("bat", pos + Vec2::new(0.0, 2.0));
+
+    game.tick(60);
+
+    let distance = game
+        .enemy_pos(first)
+        .unwrap()
+        .distance(game.enemy_pos(second).unwrap());
+    assert!(distance > 20.0, "bats are only {} apart", distance);
+}
+
+#[test]
+fn enemies_on_the_same_spot_are_pushed_apart() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(700.0, 0.0);
+    let first = game.spawn_enemy("archer", pos);
+    let second = game.spawn_enemy("archer", pos);
+
+    game.tick(60);
+
+    let distance = game
+        .enemy_pos(first)
+        .unwrap()
+        .distance(game.enemy_pos(second).unwrap());
+    assert!(distance > 10.0, "archers are only {} apart", distance);
+}
+
+#[test]
+fn off_screen_enemies_are_hidden() {
+    let mut game = TestApp::new();
+    let player_pos = game.player_pos();
+    game.app().world.spawn((
+        MainCamera,
+        GlobalTransform::from_translation(player_pos.extend(0.0)),
+        OrthographicProjection {
+            area: Rect::new(-400.0, -300.0, 400.0, 300.0),
+            ..default()
+        },
+    ));
+    let near = game.spawn_enemy("shielded", player_pos + Vec2::new(200.0, 0.0));
+    let far = game.spawn_enemy("shielded", player_pos + Vec2::new(850.0, 0.0));
+
+    game.tick(2);
+
+    let visibility =
+        |game: &mut TestApp, entity| *game.app().world.get::<Visibility>(entity).unwrap();
+    assert_eq!(visibility(&mut game, near), Visibility::Inherited);
+    assert_eq!(visibility(&mut game, far), Visibility::Hidden);
+}
+
+#[test]
+fn quick_kills_build_a_combo_that_multiplies_the_score() {
+    let mut game = TestApp::new();
+    let player_pos = game.player_pos();
+    let mut bat_score = 0;
+    for i in 0..6 {
+        let offset = Vec2::new(50.0 + 4.0 * i as f32, -10.0 + 4.0 * i as f32);
+        let bat = game.spawn_enemy("bat", player_pos + offset);
+        bat_score = game.enemy(bat).unwrap().score;
+    }
+
+    game.strike(Vec2::X);
+    game.tick(10);
+
+    assert_eq!(game.kills(), 6);
+    assert_eq!(game.combo().count(), 6);
+    assert!(game.combo().multiplier() > 1.0);
+    assert!(game.score() > 6 * bat_score);
+
+    game.tick(300);
+    assert!(!game.combo().active());
+    assert_eq!(game.combo().count(), 0);
+}
+
+#[test]
+fn the_multiplier_steps_down_once_the_combo_runs_out() {
+    let mut game = TestApp::new();
+    {
+        let mut combo = game.app().world.resource_mut::<Combo>();
+        for _ in 0..15 {
+            combo.register_kill(Some(HitSource::Strike), 100);
+        }
+    }
+    assert_eq!(game.combo().multiplier(), 2.5);
+
+    game.tick(160);
+    assert_eq!(game.combo().count(), 0);
+    assert_eq!(game.combo().multiplier(), 2.0);
+
+    game.tick(150);
+    assert_eq!(game.combo().multiplier(), 1.5);
+
+    game.tick(300);
+    assert_eq!(game.combo().multiplier(), 1.0);
+    assert!(!game.combo().active());
+}
+
+#[test]
+fn run_stats_track_kills_and_dashes() {
+    let mut game = TestApp::new();
+    let pos = game.player_pos() + Vec2::new(60.0, 0.0);
+    game.spawn_enemy("bat", pos);
+
+    game.strike(Vec2::X);
+    game.tick(10);
+    game.dash(Vec2::X);
+    game.tick(20);
+
+    let run_stats = game.run_stats();
+    assert_eq!(run_stats.kills_by_enemy.get("bat"), Some(&1));
+    assert_eq!(run_stats.strike_kills, vec![1]);
+    assert_eq!(run_stats.dashes_used, 1);
+    assert_eq!(run_stats.highest_combo, 1);
+    assert!(run_stats.distance_travelled > 0.0);
+}
+
+#[test]
+fn daily_mode_seeds_the_run_from_the_date() {
+    let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+    let mut game = TestApp::new();
+    let app = game.app();
+    app.insert_resource(SeedOverride(None))
+        .insert_resource(DateOverride(Some(date)))
+        .insert_resource(GameMode::Daily);
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Restart);
+    app.update();
+
+    assert_eq!(app.world.resource::<DailyDate>().0, Some(date));
+    assert_eq!(app.world.resource::<Seed>().0, daily_seed(date));
+    assert_ne!(daily_seed(date), daily_seed(date.succ_opt().unwrap()));
+}
+
+#[test]
+fn seed_override_turns_daily_runs_into_ordinary_runs() {
+    let mut game = TestApp::new();
+    let app = game.app();
+    app.insert_resource(SeedOverride(Some(42)))
+        .insert_resource(GameMode::Daily);
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Restart);
+    app.update();
+
+    assert_eq!(app.world.resource::<DailyDate>().0, None);
+    assert_eq!(app.world.resource::<Seed>().0, 42);
+}
+
+#[test]
+fn pausing_freezes_the_player_and_the_speed_timer() {
+    fn set_pause_state(game: &mut TestApp, state: PauseState) {
+        game.app()
+            .world
+            .resource_mut::<NextState<PauseState>>()
+            .set(state);
+        game.tick(1);
+    }
+    fn elapsed(game: &mut TestApp) -> f32 {
+        game.app().world.resource::<SpeedTimer>().elapsed
+    }
+
+    let mut game = TestApp::new();
+    game.input().move_direction = Vec2::X;
+    game.tick(10);
+
+    set_pause_state(&mut game, PauseState::Paused);
+    let pos = game.player_pos();
+    let time = elapsed(&mut game);
+    game.tick(30);
+    assert_eq!(game.player_pos(), pos);
+    assert_eq!(elapsed(&mut game), time);
+
+    set_pause_state(&mut game, PauseState::Running);
+    game.tick(10);
+    assert!(game.player_pos().x > pos.x);
+    assert!(elapsed(&mut game) > time);
+}
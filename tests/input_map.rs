@@ -0,0 +1,111 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadInfo};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+
+use insta_kill::player::input_map::{ActionInput, InputAction, InputBinding, InputMap};
+
+#[test]
+fn only_rebound_actions_are_stored() {
+    let mut input_map = InputMap::default();
+    input_map.rebind(InputAction::Dash, InputBinding::Key(KeyCode::E));
+
+    let stored = ron::to_string(&input_map).unwrap();
+    assert!(stored.contains("Dash"));
+    assert!(!stored.contains("Attack"));
+
+    let loaded: InputMap = ron::from_str(&stored).unwrap();
+    assert_eq!(
+        loaded.bindings(InputAction::Dash),
+        input_map.bindings(InputAction::Dash)
+    );
+    assert_eq!(
+        loaded.bindings(InputAction::Attack),
+        InputMap::default().bindings(InputAction::Attack)
+    );
+}
+
+#[test]
+fn unversioned_maps_drop_stale_defaults() {
+    let stored = "(bindings: { Attack: [Mouse(Left)], MoveLeft: [Key(Q)] })";
+
+    let loaded: InputMap = ron::from_str(stored).unwrap();
+    assert_eq!(
+        loaded.bindings(InputAction::Attack),
+        InputMap::default().bindings(InputAction::Attack)
+    );
+    assert_eq!(
+        loaded.bindings(InputAction::MoveLeft),
+        &[InputBinding::Key(KeyCode::Q)]
+    );
+}
+
+#[test]
+fn unversioned_maps_free_the_dpad_for_paging() {
+    let stored = "(bindings: { SwitchTab: [Key(Tab), Gamepad(DPadRight)] })";
+
+    let loaded: InputMap = ron::from_str(stored).unwrap();
+    let dpad_right = InputBinding::Gamepad(GamepadButtonType::DPadRight);
+    assert!(!loaded
+        .bindings(InputAction::SwitchTab)
+        .contains(&dpad_right));
+    assert!(loaded.bindings(InputAction::NextPage).contains(&dpad_right));
+}
+
+#[test]
+fn unversioned_maps_free_the_dpad_for_the_menus() {
+    let stored = "(bindings: { ToggleFullscreen: [Key(B), Gamepad(DPadUp)] })";
+
+    let loaded: InputMap = ron::from_str(stored).unwrap();
+    assert_eq!(
+        loaded.bindings(InputAction::ToggleFullscreen),
+        InputMap::default().bindings(InputAction::ToggleFullscreen)
+    );
+}
+
+#[test]
+fn navigating_the_menus_does_not_toggle_fullscreen() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin))
+        .init_resource::<InputMap>();
+
+    let gamepad = Gamepad::new(0);
+    app.world.send_event(GamepadConnectionEvent::new(
+        gamepad,
+        GamepadConnection::Connected(GamepadInfo {
+            name: "gamepad".to_string(),
+        }),
+    ));
+    app.update();
+
+    let input_map = InputMap::default();
+    for action in [
+        InputAction::MenuUp,
+        InputAction::MenuDown,
+        InputAction::MenuLeft,
+        InputAction::MenuRight,
+        InputAction::Confirm,
+    ] {
+        for binding in input_map.bindings(action) {
+            match *binding {
+                InputBinding::Key(key) => app.world.resource_mut::<Input<KeyCode>>().press(key),
+                InputBinding::Mouse(button) => {
+                    app.world.resource_mut::<Input<MouseButton>>().press(button)
+                }
+                InputBinding::Gamepad(button_type) => app
+                    .world
+                    .resource_mut::<Input<GamepadButton>>()
+                    .press(GamepadButton::new(gamepad, button_type)),
+            }
+        }
+    }
+
+    let (menu_up, fullscreen) = app.world.run_system_once(|actions: ActionInput| {
+        (
+            actions.just_pressed(InputAction::MenuUp),
+            actions.just_pressed(InputAction::ToggleFullscreen),
+        )
+    });
+    assert!(menu_up);
+    assert!(!fullscreen);
+}
@@ -0,0 +1,190 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use chrono::NaiveDate;
+
+use insta_kill::player::stats::RunStats;
+use insta_kill::protocol::{LeaderboardResponse, ScoreSubmission, SubmissionResponse};
+use insta_kill::world::daily::daily_seed;
+
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The bundled leaderboard server, killed when dropped.
+struct Server {
+    child: Child,
+    port: u16,
+}
+
+impl Server {
+    fn start() -> Self {
+        let port = TcpListener::bind(("127.0.0.1", 0))
+            .and_then(|listener| listener.local_addr())
+            .unwrap()
+            .port();
+        let child = Command::new(env!("CARGO_BIN_EXE_leaderboard_server"))
+            .args(["--port", &port.to_string()])
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap();
+
+        let start = Instant::now();
+        while TcpStream::connect(("127.0.0.1", port)).is_err() {
+            assert!(start.elapsed() < STARTUP_TIMEOUT, "server didn't start");
+            thread::sleep(Duration::from_millis(20));
+        }
+        Self { child, port }
+    }
+
+    /// Send a request and return the status code and the body of the response.
+    fn request(&self, method: &str, path: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(("127.0.0.1", self.port)).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, body.to_string())
+    }
+
+    fn submit(&self, submission: &ScoreSubmission) -> u64 {
+        let (status, body) = self.request(
+            "POST",
+            "/leaderboard",
+            &serde_json::to_string(submission).unwrap(),
+        );
+        assert_eq!(status, 200, "{}", body);
+        serde_json::from_str::<SubmissionResponse>(&body)
+            .unwrap()
+            .id
+    }
+
+    fn leaderboard(&self, query: &str) -> LeaderboardResponse {
+        let (status, body) = self.request("GET", &format!("/leaderboard?{}", query), "");
+        assert_eq!(status, 200, "{}", body);
+        serde_json::from_str(&body).unwrap()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn submission(name: &str, score: u32) -> ScoreSubmission {
+    ScoreSubmission {
+        name: name.to_string(),
+        score,
+        kills: 14,
+        time: 83.5,
+        seed: 7,
+        daily: None,
+        stats: RunStats::default(),
+        replay: None,
+    }
+}
+
+#[test]
+fn submitted_scores_are_listed() {
+    let server = Server::start();
+    let submission = submission("tester", 1200);
+    let id = server.submit(&submission);
+
+    let response = server.leaderboard("");
+    assert_eq!(response.total, 1);
+
+    let entry = &response.entries[0];
+    assert_eq!(entry.id, id);
+    assert_eq!(entry.rank, 0);
+    assert_eq!(entry.name, submission.name);
+    assert_eq!(entry.score, submission.score);
+    assert_eq!(entry.kills, submission.kills);
+    assert_eq!(entry.time, submission.time);
+}
+
+#[test]
+fn malformed_submissions_are_rejected() {
+    let server = Server::start();
+
+    let (status, _) = server.request("POST", "/leaderboard", "{\"name\": \"tester\"}");
+    assert_eq!(status, 400);
+
+    assert_eq!(server.leaderboard("").total, 0);
+}
+
+#[test]
+fn pages_are_ranked_and_include_the_own_entry() {
+    let server = Server::start();
+    server.submit(&submission("first", 300));
+    let own = server.submit(&submission("third", 100));
+    server.submit(&submission("second", 200));
+
+    let response = server.leaderboard(&format!("period=week&offset=1&limit=1&id={}", own));
+    assert_eq!(response.total, 3);
+    assert_eq!(response.entries.len(), 1);
+    assert_eq!(response.entries[0].name, "second");
+    assert_eq!(response.entries[0].rank, 1);
+
+    let own_entry = response.own.unwrap();
+    assert_eq!(own_entry.id, own);
+    assert_eq!(own_entry.rank, 2);
+}
+
+#[test]
+fn daily_runs_have_their_own_leaderboard() {
+    let server = Server::start();
+    let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+    server.submit(&submission("endless", 500));
+
+    let mut daily = submission("daily", 300);
+    daily.daily = Some("2024-02-29".to_string());
+    daily.seed = daily_seed(date);
+    let (status, _) = server.request(
+        "POST",
+        "/leaderboard",
+        &serde_json::to_string(&ScoreSubmission {
+            seed: daily.seed + 1,
+            ..daily.clone()
+        })
+        .unwrap(),
+    );
+    assert_eq!(status, 403);
+    server.submit(&daily);
+
+    let response = server.leaderboard("daily=2024-02-29");
+    assert_eq!(response.total, 1);
+    assert_eq!(response.entries[0].name, "daily");
+
+    let response = server.leaderboard("");
+    assert_eq!(response.total, 1);
+    assert_eq!(response.entries[0].name, "endless");
+}
+
+#[test]
+fn names_and_stats_survive_the_round_trip() {
+    let server = Server::start();
+    let mut submission = submission("a/b?c&d=e %20", 900);
+    submission
+        .stats
+        .kills_by_enemy
+        .insert("bat".to_string(), 14);
+    submission.stats.strike_kills = vec![10, 3, 1];
+    submission.stats.distance_travelled = 1234.5;
+    server.submit(&submission);
+
+    let response = server.leaderboard("period=all&offset=0&limit=10");
+    assert_eq!(response.entries[0].name, submission.name);
+}
@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+
+use insta_kill::replay::{Replay, ReplayError, ReplayTick};
+
+fn ticks() -> Vec<ReplayTick> {
+    let idle = ReplayTick::default();
+    let moving = ReplayTick {
+        move_direction: Vec2::new(0.6, -0.8),
+        mouse_coords: Vec2::new(120.5, -33.25),
+        ..default()
+    };
+    let striking = ReplayTick {
+        attack: true,
+        aim_stick: Some(Vec2::new(-1.0, 0.0)),
+        ..moving
+    };
+
+    let mut ticks = vec![idle; 3];
+    ticks.extend(std::iter::repeat(moving).take(40));
+    ticks.push(striking);
+    ticks.push(ReplayTick {
+        dash: true,
+        hook: true,
+        ..moving
+    });
+    ticks.push(idle);
+    ticks
+}
+
+#[test]
+fn replays_survive_the_round_trip() {
+    let replay = Replay::new(1234, ticks());
+
+    let loaded = Replay::from_bytes(&replay.to_bytes()).unwrap();
+    assert_eq!(loaded.seed, replay.seed);
+    assert_eq!(loaded.game_version, replay.game_version);
+    assert_eq!(loaded.ticks, replay.ticks);
+}
+
+#[test]
+fn runs_longer_than_a_repetition_count_are_split() {
+    let ticks = vec![ReplayTick::default(); u16::MAX as usize * 2 + 10];
+    let replay = Replay::new(7, ticks);
+
+    let loaded = Replay::from_bytes(&replay.to_bytes()).unwrap();
+    assert_eq!(loaded.ticks.len(), replay.ticks.len());
+}
+
+#[test]
+fn truncated_replays_are_rejected() {
+    let bytes = Replay::new(1234, ticks()).to_bytes();
+
+    for len in [0, 3, 10, bytes.len() / 2, bytes.len() - 1] {
+        assert!(
+            matches!(
+                Replay::from_bytes(&bytes[..len]),
+                Err(ReplayError::Truncated)
+            ),
+            "a replay cut at {} bytes was accepted",
+            len
+        );
+    }
+}
+
+#[test]
+fn files_without_the_magic_are_rejected() {
+    let mut bytes = Replay::new(1234, ticks()).to_bytes();
+    bytes[0] = b'X';
+
+    assert!(matches!(
+        Replay::from_bytes(&bytes),
+        Err(ReplayError::InvalidMagic)
+    ));
+}
+
+#[test]
+fn other_format_versions_are_rejected() {
+    let mut bytes = Replay::new(1234, ticks()).to_bytes();
+    bytes[4] = bytes[4].wrapping_add(1);
+
+    assert!(matches!(
+        Replay::from_bytes(&bytes),
+        Err(ReplayError::UnsupportedFormat(_))
+    ));
+}
+
+#[test]
+fn other_game_versions_are_rejected() {
+    let mut replay = Replay::new(1234, ticks());
+    replay.game_version = "0.0.0".to_string();
+
+    assert!(matches!(
+        Replay::from_bytes(&replay.to_bytes()),
+        Err(ReplayError::GameVersionMismatch(version)) if version == "0.0.0"
+    ));
+}
+
+#[test]
+fn huge_tick_counts_with_few_bytes_are_rejected() {
+    let mut bytes = Replay::new(1234, Vec::new()).to_bytes();
+    // Without ticks the file ends with the tick count.
+    let len = bytes.len();
+    bytes[len - 4..].copy_from_slice(&u32::MAX.to_le_bytes());
+
+    assert!(matches!(
+        Replay::from_bytes(&bytes),
+        Err(ReplayError::Truncated)
+    ));
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+
+use insta_kill::player::input_map::{InputAction, InputBinding, InputMap};
+use insta_kill::settings::Settings;
+
+#[test]
+fn settings_survive_the_round_trip() {
+    let mut settings = Settings {
+        main_volume: 0.3,
+        fullscreen: true,
+        screen_shake: 0.0,
+        vignette: false,
+        username: "tester".to_string(),
+        ..default()
+    };
+    settings
+        .input_map
+        .rebind(InputAction::Dash, InputBinding::Key(KeyCode::E));
+
+    let stored = ron::to_string(&settings).unwrap();
+    let loaded: Settings = ron::from_str(&stored).unwrap();
+    assert_eq!(loaded.main_volume, settings.main_volume);
+    assert_eq!(loaded.music_volume, settings.music_volume);
+    assert_eq!(loaded.fullscreen, settings.fullscreen);
+    assert_eq!(loaded.screen_shake, settings.screen_shake);
+    assert_eq!(loaded.vignette, settings.vignette);
+    assert_eq!(loaded.username, settings.username);
+    assert_eq!(
+        loaded.input_map.bindings(InputAction::Dash),
+        &[InputBinding::Key(KeyCode::E)]
+    );
+}
+
+#[test]
+fn missing_settings_fall_back_to_the_defaults() {
+    // Settings stored before the vignette and screen shake options existed.
+    let stored = "(main_volume: 0.2, username: \"tester\")";
+
+    let loaded: Settings = ron::from_str(stored).unwrap();
+    let default = Settings::default();
+    assert_eq!(loaded.main_volume, 0.2);
+    assert_eq!(loaded.username, "tester");
+    assert_eq!(loaded.screen_shake, default.screen_shake);
+    assert_eq!(loaded.vignette, default.vignette);
+    assert_eq!(
+        loaded.input_map.bindings(InputAction::Attack),
+        InputMap::default().bindings(InputAction::Attack)
+    );
+}
+
+#[test]
+fn stored_input_maps_are_migrated() {
+    // An unversioned input map, stored in full with the defaults of the time.
+    let stored = "(input_map: (bindings: { Attack: [Mouse(Left)], MoveLeft: [Key(Q)] }))";
+
+    let loaded: Settings = ron::from_str(stored).unwrap();
+    assert_eq!(
+        loaded.input_map.bindings(InputAction::Attack),
+        InputMap::default().bindings(InputAction::Attack)
+    );
+    assert_eq!(
+        loaded.input_map.bindings(InputAction::MoveLeft),
+        &[InputBinding::Key(KeyCode::Q)]
+    );
+}
+
+#[test]
+fn the_window_mode_follows_the_settings() {
+    let settings = Settings {
+        fullscreen: true,
+        ..default()
+    };
+    assert_eq!(settings.window_mode(), WindowMode::Fullscreen);
+    assert_eq!(Settings::default().window_mode(), WindowMode::Windowed);
+}